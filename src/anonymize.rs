@@ -0,0 +1,85 @@
+//! Shareable stand-ins for real puzzle inputs for `aoc22 anonymize`, driven by each day's
+//! [`Solution::anonymize`].
+
+use crate::gen::Rng;
+use crate::{Answer, DynSolution, Part};
+
+/// An anonymized input and how each part's answer compares with the original's
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Anonymized {
+    pub input: String,
+    /// Answer to the original input, the answer that implies for the anonymized one after
+    /// [`DynSolution::relabel_answer`], then the anonymized input's actual answer
+    pub answers: Vec<(Part, Answer, Answer, Answer)>,
+}
+
+impl Anonymized {
+    pub fn preserved(&self) -> bool {
+        self.answers.iter().all(|(_, _, expected, anonymized)| expected == anonymized)
+    }
+
+    /// One line per part, in the style of `watch`
+    pub fn report(&self) -> Vec<String> {
+        self.answers
+            .iter()
+            .map(|(part, original, expected, anonymized)| {
+                if expected != anonymized {
+                    format!("part {}: {} -> {}, expected {} (changed)", part, original, anonymized, expected)
+                } else if original != anonymized {
+                    format!("part {}: {} -> {} (preserved, relabelled)", part, original, anonymized)
+                } else {
+                    format!("part {}: {} (preserved)", part, original)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Anonymize a day's input and solve both versions to compare the answers
+pub fn anonymize(solution: &dyn DynSolution, input: &str, seed: u64) -> Result<Anonymized, String> {
+    let parsed = solution.parse(input).map_err(|e| e.to_string())?;
+    let anonymized = solution
+        .anonymize(input, &mut Rng::new(seed))
+        .ok_or_else(|| format!("day {} has no anonymizer", solution.day()))?;
+    let parsed_anonymized = solution
+        .parse(&anonymized)
+        .map_err(|e| format!("anonymized input no longer parses: {}", e))?;
+    let answers = Part::both()
+        .into_iter()
+        .map(|part| {
+            let original = solution.solve(parsed.as_ref(), part);
+            let expected = solution.relabel_answer(input, &anonymized, original.clone());
+            (part, original, expected, solution.solve(parsed_anonymized.as_ref(), part))
+        })
+        .collect();
+    Ok(Anonymized { input: anonymized, answers })
+}
+
+#[test]
+fn test_anonymized_examples() {
+    for solution in crate::SOLUTIONS.iter() {
+        let example = solution.examples()[0];
+        // Days fresh from `aoc22 new` have no anonymizer yet
+        if solution.anonymize(example.input, &mut Rng::new(0)).is_none() {
+            continue;
+        }
+        for seed in 0..3 {
+            let anonymized = anonymize(*solution, example.input, seed).unwrap();
+            assert!(anonymized.preserved(), "day {} seed {}: {:?}", solution.day(), seed, anonymized.report());
+        }
+    }
+
+    // Day 5's answers are crate letters, relabelled along with the crates
+    let day5 = crate::find_solution(5).unwrap();
+    let anonymized = anonymize(day5, day5.examples()[0].input, 1).unwrap();
+    assert!(anonymized.answers.iter().all(|(_, original, expected, actual)| expected == actual && original != actual));
+
+    let day7 = crate::find_solution(7).unwrap();
+    let example = day7.examples()[0].input;
+    let anonymized = anonymize(day7, example, 0).unwrap();
+    assert_ne!(anonymized.input, example);
+    assert_eq!(anonymized.input.lines().count(), example.lines().count());
+    // More one-letter names than there are letters still get distinct replacements
+    let many: String = "$ cd /\n$ ls\n".to_string() + &('a'..='z').chain('0'..='2').map(|c| format!("10 {}\n", c)).collect::<String>();
+    assert!(anonymize(day7, &many, 0).unwrap().preserved());
+}
@@ -0,0 +1,231 @@
+//! Known-good answers, keyed by day, part and a hash of the input they were computed from.
+//!
+//! Stored as a small subset of TOML so the file stays reviewable in diffs:
+//!
+//! ```toml
+//! [[answer]]
+//! day = 1
+//! part = 1
+//! input = "9c1a5b3e07f4d2a1"
+//! answer = "72718"
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::input::data_file;
+use crate::toml::{self, Value};
+use crate::Part;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Default location of the answers file, see [`data_file`]
+pub fn default_path() -> PathBuf {
+    data_file(ANSWERS_FILE)
+}
+
+/// FNV-1a hash of the input with line endings normalised, as 16 hex digits
+pub fn input_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &byte in input.replace("\r\n", "\n").as_bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnswerRecord {
+    pub day: u32,
+    pub part: Part,
+    pub input_hash: String,
+    pub answer: String,
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io { path: PathBuf, source: io::Error },
+    Syntax { line: usize, message: String },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            AnswersError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+impl From<toml::SyntaxError> for AnswersError {
+    fn from(error: toml::SyntaxError) -> AnswersError {
+        AnswersError::Syntax { line: error.line, message: error.message }
+    }
+}
+
+/// Collection of known answers
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnswerDb {
+    records: Vec<AnswerRecord>,
+}
+
+/// Fields of an `[[answer]]` table seen so far
+#[derive(Default)]
+struct PartialRecord {
+    line: usize,
+    day: Option<u32>,
+    part: Option<Part>,
+    input_hash: Option<String>,
+    answer: Option<String>,
+}
+
+impl PartialRecord {
+    fn finish(self) -> Result<AnswerRecord, AnswersError> {
+        let missing = |key: &str| AnswersError::Syntax {
+            line: self.line,
+            message: format!("[[answer]] is missing '{}'", key),
+        };
+        Ok(AnswerRecord {
+            day: self.day.ok_or_else(|| missing("day"))?,
+            part: self.part.ok_or_else(|| missing("part"))?,
+            input_hash: self.input_hash.clone().ok_or_else(|| missing("input"))?,
+            answer: self.answer.clone().ok_or_else(|| missing("answer"))?,
+        })
+    }
+}
+
+impl AnswerDb {
+    pub fn new() -> AnswerDb {
+        AnswerDb::default()
+    }
+
+    pub fn parse(text: &str) -> Result<AnswerDb, AnswersError> {
+        let mut db = AnswerDb::new();
+        for table in toml::parse(text)? {
+            let syntax = |line: usize, message: String| AnswersError::Syntax { line, message };
+            match (table.name.as_str(), table.array) {
+                ("answer", true) => {},
+                ("", _) => match table.entries.first() {
+                    Some(entry) => {
+                        return Err(syntax(entry.line, format!("'{}' outside of an [[answer]] table", entry.key)));
+                    },
+                    None => continue,
+                },
+                (name, _) => return Err(syntax(table.line, format!("unexpected table '{}'", name))),
+            }
+            let mut record = PartialRecord { line: table.line, ..Default::default() };
+            for entry in table.entries {
+                let invalid = |what: &str| syntax(entry.line, format!("invalid {} {}", what, entry.value));
+                match (entry.key.as_str(), &entry.value) {
+                    ("day", Value::Integer(day)) => {
+                        record.day = Some(u32::try_from(*day).map_err(|_| invalid("day"))?);
+                    },
+                    ("part", Value::Integer(1)) => record.part = Some(Part::One),
+                    ("part", Value::Integer(2)) => record.part = Some(Part::Two),
+                    ("input", Value::String(hash)) => record.input_hash = Some(hash.clone()),
+                    ("answer", Value::String(answer)) => record.answer = Some(answer.clone()),
+                    ("day", _) => return Err(invalid("day")),
+                    ("part", _) => return Err(syntax(entry.line, format!("invalid part {}, expected 1 or 2", entry.value))),
+                    ("input" | "answer", _) => return Err(invalid("string")),
+                    (key, _) => return Err(syntax(entry.line, format!("unknown key '{}'", key))),
+                }
+            }
+            db.records.push(record.finish()?);
+        }
+        Ok(db)
+    }
+
+    /// Load answers from a file; a missing file is an empty database
+    pub fn load(path: &Path) -> Result<AnswerDb, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(text) => AnswerDb::parse(&text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(AnswerDb::new()),
+            Err(source) => Err(AnswersError::Io { path: path.to_path_buf(), source }),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        fs::write(path, self.to_toml()).map_err(|source| AnswersError::Io { path: path.to_path_buf(), source })
+    }
+
+    pub fn records(&self) -> &[AnswerRecord] {
+        &self.records
+    }
+
+    pub fn get(&self, day: u32, part: Part, input_hash: &str) -> Option<&str> {
+        self.records
+            .iter()
+            .find(|r| r.day == day && r.part == part && r.input_hash == input_hash)
+            .map(|r| r.answer.as_str())
+    }
+
+    /// Add or replace the answer for a day, part and input
+    pub fn insert(&mut self, record: AnswerRecord) {
+        match self.records.iter_mut().find(|r| {
+            r.day == record.day && r.part == record.part && r.input_hash == record.input_hash
+        }) {
+            Some(existing) => *existing = record,
+            None => self.records.push(record),
+        }
+    }
+
+    /// Serialise sorted by day and part so the file diffs cleanly
+    pub fn to_toml(&self) -> String {
+        let mut records = self.records.clone();
+        records.sort_by(|a, b| (a.day, a.part, &a.input_hash).cmp(&(b.day, b.part, &b.input_hash)));
+        let mut out = String::from("# Known answers, checked by `aoc22 verify`\n");
+        for record in records {
+            out += &format!(
+                "\n[[answer]]\nday = {}\npart = {}\ninput = {}\nanswer = {}\n",
+                record.day,
+                record.part,
+                toml::quote(&record.input_hash),
+                toml::quote(&record.answer),
+            );
+        }
+        out
+    }
+}
+
+/// Result of checking one computed answer against the database
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Mismatch { expected: String },
+    /// Nothing recorded for this day, part and input
+    Unknown,
+}
+
+impl AnswerDb {
+    pub fn check(&self, day: u32, part: Part, input_hash: &str, actual: &str) -> Verdict {
+        match self.get(day, part, input_hash) {
+            Some(expected) if expected == actual => Verdict::Match,
+            Some(expected) => Verdict::Mismatch { expected: expected.to_string() },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[test]
+fn test_answers_round_trip() {
+    let mut db = AnswerDb::new();
+    db.insert(AnswerRecord { day: 5, part: Part::Two, input_hash: input_hash("b"), answer: "RNRG\"X".to_string() });
+    db.insert(AnswerRecord { day: 1, part: Part::One, input_hash: input_hash("a"), answer: "72718".to_string() });
+    let parsed = AnswerDb::parse(&db.to_toml()).unwrap();
+    assert_eq!(parsed.get(1, Part::One, &input_hash("a")), Some("72718"));
+    assert_eq!(parsed.check(5, Part::Two, &input_hash("b"), "RNRG\"X"), Verdict::Match);
+    assert_eq!(parsed.check(5, Part::Two, &input_hash("b"), "other"), Verdict::Mismatch { expected: "RNRG\"X".to_string() });
+    assert_eq!(parsed.check(5, Part::One, &input_hash("b"), "x"), Verdict::Unknown);
+    assert_eq!(input_hash("1\r\n2\r\n"), input_hash("1\n2\n"));
+}
+
+#[test]
+fn test_answers_syntax_errors() {
+    assert!(matches!(AnswerDb::parse("day = 1"), Err(AnswersError::Syntax { line: 1, .. })));
+    assert!(matches!(AnswerDb::parse("[[answer]]\nday = 1\nbogus = 2"), Err(AnswersError::Syntax { line: 3, .. })));
+    assert!(matches!(AnswerDb::parse("[[answer]]\nday = 1\npart = 1\ninput = \"x\""), Err(AnswersError::Syntax { line: 1, .. })));
+}
@@ -0,0 +1,183 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::output::{csv_field, json_string, Format};
+use crate::{DynSolution, ParseError, Part};
+
+/// Summary statistics over repeated timings of one phase
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "Cannot summarise zero samples");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        // Sample standard deviation, zero for a single sample
+        let variance = if n > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        Stats {
+            samples: n,
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+/// Phase of a solution that gets timed separately
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part(Part::One) => "part1",
+            Phase::Part(Part::Two) => "part2",
+        }
+    }
+}
+
+/// Timings of every phase of one day
+#[derive(Clone, Debug)]
+pub struct DayBench {
+    pub day: u32,
+    pub phases: Vec<(Phase, Stats)>,
+}
+
+fn time_samples<F: FnMut()>(iterations: usize, mut f: F) -> Vec<Duration> {
+    // One untimed warm-up run so lazy allocations don't skew the first sample
+    f();
+    (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect()
+}
+
+/// Run parse, part 1 and part 2 `iterations` times each and summarise the timings
+pub fn bench_day(solution: &dyn DynSolution, input: &str, iterations: usize) -> Result<DayBench, ParseError> {
+    let iterations = iterations.max(1);
+    let mut phases = Vec::new();
+    // Parse once up front so malformed input is reported rather than timed
+    let parsed = solution.parse(input)?;
+    let parse = time_samples(iterations, || {
+        let _ = black_box(solution.parse(black_box(input)));
+    });
+    phases.push((Phase::Parse, Stats::from_samples(&parse)));
+
+    for part in Part::both() {
+        let samples = time_samples(iterations, || {
+            black_box(solution.solve(black_box(parsed.as_ref()), part));
+        });
+        phases.push((Phase::Part(part), Stats::from_samples(&samples)));
+    }
+    Ok(DayBench { day: solution.day(), phases })
+}
+
+/// Render benchmark results in the requested format
+pub fn render(results: &[DayBench], format: Format) -> String {
+    match format {
+        Format::Text => render_text(results),
+        Format::Csv => render_csv(results),
+        Format::Json => render_json(results),
+    }
+}
+
+fn render_text(results: &[DayBench]) -> String {
+    let mut out = format!(
+        "{:>3}  {:<6} {:>7} {:>12} {:>12} {:>12} {:>12}\n",
+        "day", "phase", "samples", "min", "median", "mean", "stddev"
+    );
+    for result in results {
+        for (phase, stats) in &result.phases {
+            out += &format!(
+                "{:>3}  {:<6} {:>7} {:>12} {:>12} {:>12} {:>12}\n",
+                result.day,
+                phase.name(),
+                stats.samples,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.mean),
+                format!("{:.2?}", stats.stddev),
+            );
+        }
+    }
+    out
+}
+
+fn render_csv(results: &[DayBench]) -> String {
+    let mut out = String::from("day,phase,samples,min_ns,median_ns,mean_ns,stddev_ns\n");
+    for result in results {
+        for (phase, stats) in &result.phases {
+            out += &format!(
+                "{},{},{},{},{},{},{}\n",
+                result.day,
+                csv_field(phase.name()),
+                stats.samples,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.stddev.as_nanos(),
+            );
+        }
+    }
+    out
+}
+
+fn render_json(results: &[DayBench]) -> String {
+    let records: Vec<String> = results
+        .iter()
+        .flat_map(|result| {
+            result.phases.iter().map(move |(phase, stats)| {
+                format!(
+                    "{{\"day\":{},\"phase\":{},\"samples\":{},\"min_ns\":{},\"median_ns\":{},\"mean_ns\":{},\"stddev_ns\":{}}}",
+                    result.day,
+                    json_string(phase.name()),
+                    stats.samples,
+                    stats.min.as_nanos(),
+                    stats.median.as_nanos(),
+                    stats.mean.as_nanos(),
+                    stats.stddev.as_nanos(),
+                )
+            })
+        })
+        .collect();
+    format!("[{}]\n", records.join(","))
+}
+
+#[test]
+fn test_stats_from_samples() {
+    let samples: Vec<Duration> = [4, 1, 3, 2].iter().map(|&n| Duration::from_nanos(n)).collect();
+    let stats = Stats::from_samples(&samples);
+    assert_eq!(stats.samples, 4);
+    assert_eq!(stats.min, Duration::from_nanos(1));
+    assert_eq!(stats.median, Duration::from_nanos(2));
+    assert_eq!(stats.mean, Duration::from_nanos(3));
+    // sqrt(5 / 3) rounds to 1ns
+    assert_eq!(stats.stddev, Duration::from_nanos(1));
+}
@@ -1,26 +1,151 @@
-use std::fs;
+use std::env;
+use std::io::BufRead;
+use std::process;
 
+use aoc22::day01::{checked_sum, closest_k, fewest_to_reach, for_each_elf, top_totals, Calories, Day1, ReadError, Selection, Stats};
+use aoc22::input::InputSource;
+use aoc22::output::Format;
+use aoc22::{ParseError, Solution};
+
+const USAGE: &str = "usage: day1 [--top N] [--width u32|u64|u128] [--stats [--format text|json]]
+            [--closest K --target CALORIES] [--reach CALORIES] [path|-]";
+
+struct Options {
+    top: Option<usize>,
+    stats: bool,
+    format: Format,
+    /// Number of elves to pick as close as possible to the target
+    closest: Option<usize>,
+    target: Option<u128>,
+    /// Calories the fewest elves should reach
+    reach: Option<u128>,
+}
+
+fn exit_on_error<T>(result: Result<T, ReadError>, input: &InputSource) -> T {
+    match result {
+        Ok(value) => value,
+        Err(ReadError::Parse(e)) => ParseError::or_exit(Err(e), &input.to_string()),
+        Err(e @ ReadError::Overflow { .. }) => {
+            eprintln!("{}: {}, try a wider --width", input, e);
+            process::exit(1);
+        },
+        Err(ReadError::Io(e)) => {
+            eprintln!("failed to read {}: {}", input, e);
+            process::exit(1);
+        },
+    }
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(2);
+}
 
 fn main() {
-    println!("Day 1 code");
-    let contents = fs::read_to_string("day 1/input.txt")
-        .expect("Failed to read string.");
-    let contents_list: Vec<&str> = contents.split('\n').collect();
-    let mut calories = 0;
-    let mut totals = Vec::new();
-    for line in contents_list {
-        if line.is_empty() {
-            totals.push(calories);
-            calories = 0;
-            continue;
+    let mut options = Options { top: None, stats: false, format: Format::Text, closest: None, target: None, reach: None };
+    let mut width = "u32".to_string();
+    let mut input = InputSource::Day(Day1::DAY);
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => match args.next().and_then(|n| n.parse::<usize>().ok()) {
+                Some(n) if n > 0 => options.top = Some(n),
+                _ => usage_error("--top needs a positive number"),
+            },
+            "--width" => width = args.next().unwrap_or_default(),
+            "--closest" => match args.next().and_then(|k| k.parse().ok()) {
+                Some(k) if k > 0 => options.closest = Some(k),
+                _ => usage_error("--closest needs a positive number of elves"),
+            },
+            "--target" => match args.next().and_then(|target| target.parse().ok()) {
+                Some(target) => options.target = Some(target),
+                None => usage_error("--target needs a number of calories"),
+            },
+            "--reach" => match args.next().and_then(|target| target.parse().ok()) {
+                Some(target) if target > 0 => options.reach = Some(target),
+                _ => usage_error("--reach needs a positive number of calories"),
+            },
+            "--stats" => options.stats = true,
+            "--format" => match args.next().map(|format| format.parse()) {
+                Some(Ok(f @ (Format::Text | Format::Json))) => options.format = f,
+                _ => usage_error("--format needs text or json"),
+            },
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            },
+            // '-' alone is stdin, anything else starting with '-' is a mistyped flag rather than a path
+            _ if arg.starts_with('-') && arg != "-" => usage_error(&format!("Unknown option '{}'", arg)),
+            _ => input = InputSource::from_arg(&arg),
+        }
+    }
+    if options.closest.is_some() != options.target.is_some() {
+        usage_error("--closest and --target go together");
+    }
+
+    let reader = input.open().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    match width.as_str() {
+        "u32" => run::<u32>(reader, &input, &options),
+        "u64" => run::<u64>(reader, &input, &options),
+        "u128" => run::<u128>(reader, &input, &options),
+        _ => usage_error("--width needs u32, u64 or u128"),
+    }
+}
+
+fn print_selection(query: &str, selection: Option<Selection>) {
+    match selection {
+        Some(selection) => {
+            let elves: Vec<String> = selection.elves.iter().map(|index| index.to_string()).collect();
+            let quality = if selection.exact { "best possible" } else { "approximate" };
+            println!("{}: elves {} carrying {} calories ({})", query, elves.join(", "), selection.total, quality);
+        },
+        None => println!("{}: not possible", query),
+    }
+}
+
+fn run<C: Calories>(reader: impl BufRead, input: &InputSource, options: &Options) {
+    if options.stats || options.target.is_some() || options.reach.is_some() {
+        let mut elves = Vec::new();
+        exit_on_error(for_each_elf::<C, _>(reader, |elf| elves.push(elf)), input);
+        if let (Some(k), Some(target)) = (options.closest, options.target) {
+            print_selection(&format!("{} elves closest to {}", k, target), closest_k(&elves, k, target));
+        }
+        if let Some(target) = options.reach {
+            print_selection(&format!("Fewest elves reaching {}", target), fewest_to_reach(&elves, target));
         }
-        let calorie = line.parse::<u32>().expect("Line was not an integer");
-        calories += calorie;
-    }
-    totals.sort();
-    println!("Max calories count: {}", totals[totals.len() - 1]);
-    let top_3_sum: u32 = (totals[totals.len() - 3..])
-            .iter()
-            .sum();
-    println!("Total of top 3: {}", top_3_sum);
+        if !options.stats {
+            return;
+        }
+        let Some(stats) = Stats::new(&elves, options.top.unwrap_or(3)) else {
+            eprintln!("{}: no elves found", input);
+            process::exit(1);
+        };
+        match options.format {
+            Format::Json => println!("{}", stats.to_json()),
+            _ => print!("{}", stats.to_text()),
+        }
+        return;
+    }
+    println!("Day 1 code");
+    let totals: Vec<C> = exit_on_error(top_totals(reader, options.top.unwrap_or(3)), input);
+    if totals.is_empty() {
+        eprintln!("{}: no elves found", input);
+        process::exit(1);
+    }
+    let sum = checked_sum(&totals).map_or_else(|| format!("overflows {}, try a wider --width", C::NAME), |sum| sum.to_string());
+    match options.top {
+        None => {
+            println!("Max calories count: {}", totals[0]);
+            println!("Total of top 3: {}", sum);
+        },
+        Some(n) => {
+            for (rank, total) in totals.iter().enumerate() {
+                println!("{}. {}", rank + 1, total);
+            }
+            println!("Total of top {}: {}", n, sum);
+        },
+    }
 }
@@ -1,45 +1,15 @@
-use std::fs;
-use std::collections::HashMap;
+use aoc22::day06::Day6;
+use aoc22::input::InputSource;
+use aoc22::{ParseError, Solution};
 
 fn main() {
     println!("Day 6");
     // Read content
-    let content = fs::read_to_string("day 6/input.txt").unwrap();
-    let chars: Vec<char> = content.chars().collect();
+    let input = InputSource::Day(Day6::DAY);
+    let content = input.read().unwrap();
+    let chars = ParseError::or_exit(Day6.parse(&content), &input.to_string());
     // Part 1
-    let mut letters = HashMap::new();
-    letters.insert(chars[0], 1);
-    *letters.entry(chars[1]).or_insert(0) += 1;
-    *letters.entry(chars[2]).or_insert(0) += 1;
-    *letters.entry(chars[3]).or_insert(0) += 1;
-    for (i, &letter) in chars[4..].iter().enumerate() {
-        letters.retain(|_, v| *v > 0);
-        if letters.keys().len() == 4 {
-            println!("Index of first non-repeated set of 4 letters: {}", i + 4);
-            println!("{:#?}", letters);
-            break
-        }
-        if letter.is_alphabetic() {
-            *letters.entry(letter).or_insert(0) += 1;
-            *letters.entry(chars[i]).or_insert(0) -= 1;
-        }
-    }
+    println!("Index of first non-repeated set of 4 letters: {}", Day6.part1(&chars));
     // Part 2
-    // let chars: Vec<char> = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".chars().collect();
-    letters = chars[..14].iter().fold(HashMap::new(), |mut acc, &letter| {
-        *acc.entry(letter).or_insert(0) += 1;
-        acc
-    });
-    for (i, &letter) in chars[14..].iter().enumerate() {
-        letters.retain(|_, v| *v > 0);
-        if letters.keys().len() == 14 {
-            println!("Index of first non-repeated set of 14 letters: {}", i + 14);
-            println!("{:#?}", letters);
-            break
-        }
-        if letter.is_alphabetic() {
-            *letters.entry(letter).or_insert(0) += 1;
-            *letters.entry(chars[i]).or_insert(0) -= 1;
-        }
-    }
-}
\ No newline at end of file
+    println!("Index of first non-repeated set of 14 letters: {}", Day6.part2(&chars));
+}
@@ -0,0 +1,188 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config;
+use crate::http::{self, HttpError, Response};
+use crate::input::day_dir;
+
+/// Environment variable holding the site's `session` cookie
+pub const SESSION_VAR: &str = "AOC22_SESSION";
+/// Environment variable overriding the site, e.g. to point at a local stub server
+pub const BASE_URL_VAR: &str = "AOC22_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u32 = 2022;
+/// Shortest gap between two requests to the site, across runs
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+const USER_AGENT: &str = "aoc22 (github.com/pi314ever/Advent-of-Code-2022)";
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// No session token in the environment or the session file
+    NoSession { file: Option<PathBuf> },
+    Http(HttpError),
+    /// The site answered, but not with what was asked for
+    Rejected { status: u16, message: String },
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::NoSession { file } => {
+                write!(f, "no session token: set {}", SESSION_VAR)?;
+                if let Some(file) = file {
+                    write!(f, " or save it in {}", file.display())?;
+                }
+                Ok(())
+            },
+            ClientError::Http(error) => write!(f, "{}", error),
+            ClientError::Rejected { status, message } => write!(f, "site answered {}: {}", status, message),
+            ClientError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<HttpError> for ClientError {
+    fn from(error: HttpError) -> ClientError {
+        ClientError::Http(error)
+    }
+}
+
+/// File the session token can be saved in: `$XDG_CONFIG_HOME/aoc22/session` or `~/.config/aoc22/session`
+pub fn session_file() -> Option<PathBuf> {
+    Some(config::user_dir()?.join("session"))
+}
+
+/// Session token from [`SESSION_VAR`], falling back to [`session_file`]
+pub fn find_session() -> Result<String, ClientError> {
+    if let Ok(session) = env::var(SESSION_VAR) {
+        if !session.trim().is_empty() {
+            return Ok(session.trim().to_string());
+        }
+    }
+    let file = session_file();
+    match file.as_ref().map(fs::read_to_string) {
+        Some(Ok(session)) if !session.trim().is_empty() => Ok(session.trim().to_string()),
+        _ => Err(ClientError::NoSession { file }),
+    }
+}
+
+/// Where a day's input ended up
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// Already on disk, the site wasn't contacted
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Client for the puzzle site
+#[derive(Clone, Debug)]
+pub struct Client {
+    pub base_url: String,
+    pub session: String,
+    pub min_interval: Duration,
+    /// File recording when the site was last contacted, so the rate limit holds across runs
+    pub stamp_path: PathBuf,
+}
+
+impl Client {
+    /// Client for the site in [`BASE_URL_VAR`] (default the real site) using [`find_session`]
+    pub fn from_env() -> Result<Client, ClientError> {
+        Ok(Client {
+            base_url: env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string()),
+            session: find_session()?,
+            min_interval: MIN_INTERVAL,
+            stamp_path: env::temp_dir().join("aoc22-last-request"),
+        })
+    }
+
+    /// Sleep until `min_interval` has passed since the last request, then record this one
+    fn wait_turn(&self) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let last = fs::read_to_string(&self.stamp_path)
+            .ok()
+            .and_then(|stamp| stamp.trim().parse::<u64>().ok())
+            .map(Duration::from_millis);
+        if let Some(elapsed) = last.and_then(|last| now.checked_sub(last)) {
+            if elapsed < self.min_interval {
+                thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        // Failing to record the stamp only weakens the rate limit, so it isn't an error
+        let _ = fs::write(&self.stamp_path, now.as_millis().to_string());
+    }
+
+    /// Rate-limited request to a path on the site, authenticated with the session cookie
+    pub fn send(&self, method: &str, path: &str, body: Option<&str>) -> Result<Response, ClientError> {
+        self.wait_turn();
+        let url = format!("{}{}", self.base_url.trim_end_matches('/'), path);
+        let cookie = format!("session={}", self.session);
+        let mut headers = vec![("Cookie", cookie.as_str()), ("User-Agent", USER_AGENT)];
+        if body.is_some() {
+            headers.push(("Content-Type", "application/x-www-form-urlencoded"));
+        }
+        Ok(http::request(method, &url, &headers, body)?)
+    }
+
+    /// Download a day's input to `day N/input.txt` under `root`, unless it is already there
+    pub fn fetch_input(&self, day: u32, root: &Path) -> Result<Fetched, ClientError> {
+        let dir = root.join(day_dir(day));
+        let path = dir.join("input.txt");
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+        let response = self.send("GET", &format!("/{}/day/{}/input", YEAR, day), None)?;
+        let first_line = response.body.lines().next().unwrap_or("").to_string();
+        match response.status {
+            200 if !response.body.is_empty() && !first_line.starts_with("Puzzle inputs differ by user") => {},
+            404 => {
+                return Err(ClientError::Rejected { status: 404, message: format!("day {} is not unlocked yet", day) });
+            },
+            400 | 500 => {
+                return Err(ClientError::Rejected { status: response.status, message: "session token rejected".to_string() });
+            },
+            status => return Err(ClientError::Rejected { status, message: first_line }),
+        }
+        let io_error = |path: &Path| {
+            let path = path.to_path_buf();
+            move |source| ClientError::Io { path, source }
+        };
+        fs::create_dir_all(&dir).map_err(io_error(&dir))?;
+        // Write then rename so an interrupted download never looks like a cached input
+        let partial = dir.join("input.txt.part");
+        fs::write(&partial, &response.body).map_err(io_error(&partial))?;
+        fs::rename(&partial, &path).map_err(io_error(&path))?;
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+/// Client for a [`http::serve_once`] server, with session `abc123` and no rate limit
+#[cfg(test)]
+pub fn serve_client(response: &'static str, stamp_path: PathBuf) -> (Client, thread::JoinHandle<String>) {
+    let (base_url, server) = http::serve_once(response);
+    let client = Client { base_url, session: "abc123".to_string(), min_interval: Duration::ZERO, stamp_path };
+    (client, server)
+}
+
+#[test]
+fn test_fetch_input_from_stub_server() {
+    let root = env::temp_dir().join(format!("aoc22-fetch-{}", std::process::id()));
+    let (client, server) = serve_client("HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\n1\n2\n3\n", root.join("stamp"));
+    let path = root.join("day 3").join("input.txt");
+    assert_eq!(client.fetch_input(3, &root).unwrap(), Fetched::Downloaded(path.clone()));
+    let request = server.join().unwrap();
+    assert!(request.starts_with("GET /2022/day/3/input HTTP/1.0\r\n"), "{}", request);
+    assert!(request.contains("Cookie: session=abc123\r\n"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+    // The stub only answers once, so a second download would fail
+    assert_eq!(client.fetch_input(3, &root).unwrap(), Fetched::Cached(path));
+    fs::remove_dir_all(&root).unwrap();
+}
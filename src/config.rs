@@ -0,0 +1,251 @@
+//! Runner defaults from `aoc22.toml` at the project root, overridden by the user's own
+//! `~/.config/aoc22/config.toml`.
+//!
+//! ```toml
+//! [input]
+//! dirs = ["inputs"]      # searched for 'day N/input.txt', relative to the file
+//!
+//! [run]
+//! format = "json"
+//! part = 1
+//!
+//! [bench]
+//! iterations = 50
+//!
+//! [day7]
+//! disk_size = 70000000
+//! ```
+//!
+//! Command line flags and `$AOC22_INPUT_DIR` still take precedence.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::output::Format;
+use crate::toml::{self, Entry, SyntaxError, Table, Value};
+use crate::{find_solution, Part};
+
+/// Project configuration file, at the project root
+pub const PROJECT_FILE: &str = "aoc22.toml";
+
+/// Directory for per-user files: `$XDG_CONFIG_HOME/aoc22` or `~/.config/aoc22`
+pub fn user_dir() -> Option<PathBuf> {
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config.join("aoc22"))
+}
+
+/// Per-user configuration file, whose settings override the project's
+pub fn user_file() -> Option<PathBuf> {
+    Some(user_dir()?.join("config.toml"))
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io { path: PathBuf, source: io::Error },
+    Syntax { path: PathBuf, error: SyntaxError },
+    /// Well-formed, but not a setting this crate knows or not a valid value for it
+    Invalid { path: PathBuf, line: usize, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            ConfigError::Syntax { path, error } => write!(f, "{}:{}: {}", path.display(), error.line, error.message),
+            ConfigError::Invalid { path, line, message } => write!(f, "{}:{}: {}", path.display(), line, message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    /// Directories searched for `day N/input.txt`, in order
+    pub input_dirs: Vec<PathBuf>,
+    pub format: Option<Format>,
+    /// Part to run when `--part` isn't given, both when unset
+    pub part: Option<Part>,
+    pub bench_iterations: Option<usize>,
+    /// Per-day parameters by day and name
+    pub params: BTreeMap<(u32, String), i64>,
+}
+
+impl Config {
+    /// Read settings from the text of `path`, resolving relative input directories against its directory
+    pub fn parse(text: &str, path: &Path) -> Result<Config, ConfigError> {
+        let tables = toml::parse(text).map_err(|error| ConfigError::Syntax { path: path.to_path_buf(), error })?;
+        let invalid = |line: usize, message: String| ConfigError::Invalid { path: path.to_path_buf(), line, message };
+        let base = path.parent().unwrap_or(Path::new(""));
+        let mut config = Config::default();
+        for table in &tables {
+            if table.array {
+                return Err(invalid(table.line, format!("[[{}]] is not a setting, use [{}]", table.name, table.name)));
+            }
+            let day = table.name.strip_prefix("day").and_then(|day| day.parse::<u32>().ok());
+            match (table.name.as_str(), day) {
+                ("", _) => {
+                    if let Some(entry) = table.entries.first() {
+                        let message = format!("unknown key '{}', settings belong in a table such as [run]", entry.key);
+                        return Err(invalid(entry.line, message));
+                    }
+                },
+                ("input", _) => {
+                    for entry in &table.entries {
+                        check_key(table, entry, &["dirs"]).map_err(|m| invalid(entry.line, m))?;
+                        let dirs = match &entry.value {
+                            Value::Array(values) => values
+                                .iter()
+                                .map(|value| match value {
+                                    Value::String(dir) => Some(base.join(dir)),
+                                    _ => None,
+                                })
+                                .collect::<Option<Vec<PathBuf>>>(),
+                            _ => None,
+                        };
+                        config.input_dirs = dirs.ok_or_else(|| invalid(entry.line, mismatch(table, entry, "an array of strings")))?;
+                    }
+                },
+                ("run", _) => {
+                    for entry in &table.entries {
+                        check_key(table, entry, &["format", "part"]).map_err(|m| invalid(entry.line, m))?;
+                        match (entry.key.as_str(), &entry.value) {
+                            ("format", Value::String(format)) => {
+                                config.format = Some(format.parse().map_err(|m| invalid(entry.line, m))?);
+                            },
+                            ("part", Value::Integer(1)) => config.part = Some(Part::One),
+                            ("part", Value::Integer(2)) => config.part = Some(Part::Two),
+                            ("format", _) => return Err(invalid(entry.line, mismatch(table, entry, "a string"))),
+                            _ => return Err(invalid(entry.line, mismatch(table, entry, "1 or 2"))),
+                        }
+                    }
+                },
+                ("bench", _) => {
+                    for entry in &table.entries {
+                        check_key(table, entry, &["iterations"]).map_err(|m| invalid(entry.line, m))?;
+                        match entry.value {
+                            Value::Integer(n) if n > 0 => config.bench_iterations = Some(n as usize),
+                            _ => return Err(invalid(entry.line, mismatch(table, entry, "a positive integer"))),
+                        }
+                    }
+                },
+                (_, Some(day)) => {
+                    let solution = find_solution(day).ok_or_else(|| invalid(table.line, format!("day {} is not solved yet", day)))?;
+                    let names: Vec<&str> = solution.params().iter().map(|param| param.name).collect();
+                    for entry in &table.entries {
+                        check_key(table, entry, &names).map_err(|m| invalid(entry.line, m))?;
+                        let param = solution.params().iter().find(|param| param.name == entry.key).unwrap();
+                        match entry.value {
+                            Value::Integer(n) if (param.min..=param.max).contains(&n) => {
+                                config.params.insert((day, entry.key.clone()), n);
+                            },
+                            _ => {
+                                let expected = format!("an integer from {} to {}", param.min, param.max);
+                                return Err(invalid(entry.line, mismatch(table, entry, &expected)));
+                            },
+                        }
+                    }
+                },
+                (name, None) => {
+                    let message = format!("unknown table [{}], expected [input], [run], [bench] or [dayN]", name);
+                    return Err(invalid(table.line, message));
+                },
+            }
+        }
+        Ok(config)
+    }
+
+    /// Settings made in `other` replace ours, the input directories as a whole list
+    pub fn merge(&mut self, other: Config) {
+        if !other.input_dirs.is_empty() {
+            self.input_dirs = other.input_dirs;
+        }
+        self.format = other.format.or(self.format);
+        self.part = other.part.or(self.part);
+        self.bench_iterations = other.bench_iterations.or(self.bench_iterations);
+        self.params.extend(other.params);
+    }
+
+    /// Project file under `root`, then the user file, skipping whichever doesn't exist
+    pub fn load(root: &Path) -> Result<Config, ConfigError> {
+        let mut config = Config::default();
+        for path in [Some(root.join(PROJECT_FILE)), user_file()].into_iter().flatten() {
+            let text = match fs::read_to_string(&path) {
+                Ok(text) => text,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(source) => return Err(ConfigError::Io { path, source }),
+            };
+            crate::debug!("loaded config", path = path);
+            config.merge(Config::parse(&text, &path)?);
+        }
+        Ok(config)
+    }
+
+    /// A day's parameter, falling back to the default the day declares
+    pub fn param(&self, day: u32, name: &str) -> i64 {
+        if let Some(&value) = self.params.get(&(day, name.to_string())) {
+            return value;
+        }
+        let solution = find_solution(day).unwrap_or_else(|| panic!("Day {} is not registered", day));
+        match solution.params().iter().find(|param| param.name == name) {
+            Some(param) => param.default,
+            None => panic!("Day {} does not declare a parameter '{}'", day, name),
+        }
+    }
+}
+
+fn check_key(table: &Table, entry: &Entry, known: &[&str]) -> Result<(), String> {
+    if known.contains(&entry.key.as_str()) {
+        Ok(())
+    } else if known.is_empty() {
+        Err(format!("unknown key '{}' in [{}], which takes no settings", entry.key, table.name))
+    } else {
+        Err(format!("unknown key '{}' in [{}], expected one of: {}", entry.key, table.name, known.join(", ")))
+    }
+}
+
+fn mismatch(table: &Table, entry: &Entry, expected: &str) -> String {
+    format!("'{}' in [{}] must be {}, found {}", entry.key, table.name, expected, entry.value)
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Make `config` the one [`get`] returns, which only takes effect before the first [`get`]
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+/// Configuration set by [`init`], or the defaults when it wasn't called
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+#[test]
+fn test_parse_config() {
+    let path = Path::new("/repo/aoc22.toml");
+    let text = "[input]\ndirs = [\"inputs\", \"/data/aoc\"]\n\n[run]\nformat = \"csv\"\npart = 2\n\n[bench]\niterations = 5\n\n[day7]\ndisk_size = 80000000\n";
+    let config = Config::parse(text, path).unwrap();
+    assert_eq!(config.input_dirs, vec![PathBuf::from("/repo/inputs"), PathBuf::from("/data/aoc")]);
+    assert_eq!((config.format, config.part, config.bench_iterations), (Some(Format::Csv), Some(Part::Two), Some(5)));
+    assert_eq!(config.param(7, "disk_size"), 80_000_000);
+    assert_eq!(config.param(7, "space_needed"), 30_000_000);
+
+    let mut merged = config.clone();
+    merged.merge(Config::parse("[run]\nformat = \"json\"\n", path).unwrap());
+    assert_eq!((merged.format, merged.part), (Some(Format::Json), Some(Part::Two)));
+
+    let error = |text: &str| Config::parse(text, path).unwrap_err().to_string();
+    assert_eq!(error("[run]\nformats = \"csv\""), "/repo/aoc22.toml:2: unknown key 'formats' in [run], expected one of: format, part");
+    assert_eq!(error("[output]"), "/repo/aoc22.toml:1: unknown table [output], expected [input], [run], [bench] or [dayN]");
+    assert!(error("[day7]\nsize = 1").contains("unknown key 'size' in [day7], expected one of: small_dir_limit, disk_size, space_needed"));
+    assert!(error("[day5]\npart1_crane = 9002").contains("must be an integer from 9000 to 9001, found 9002"));
+    assert!(error("[day25]").contains("day 25 is not solved yet"));
+    assert!(error("format = \"csv\"").contains("settings belong in a table"));
+}
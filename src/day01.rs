@@ -0,0 +1,669 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+use crate::gen::{Generator, Rng};
+use crate::output::json_string;
+use crate::paragraph::{paragraphs, read_paragraphs};
+use crate::{Example, ParseError, Solution};
+
+/// Unsigned integer type calories are added up in, so wide stress inputs can pick a bigger one
+pub trait Calories: Copy + Ord + Default + fmt::Display + fmt::Debug + FromStr + Send + 'static {
+    /// Type name for messages, e.g. `u32`
+    const NAME: &'static str;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn to_u128(self) -> u128;
+}
+
+macro_rules! calories {
+    ($($t:ty),*) => {
+        $(
+            impl Calories for $t {
+                const NAME: &'static str = stringify!($t);
+
+                fn checked_add(self, other: $t) -> Option<$t> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn to_u128(self) -> u128 {
+                    self as u128
+                }
+            }
+        )*
+    };
+}
+
+calories!(u32, u64, u128);
+
+/// Sum of `values`, or `None` if it doesn't fit
+pub fn checked_sum<C: Calories>(values: &[C]) -> Option<C> {
+    values.iter().try_fold(C::default(), |sum, &value| sum.checked_add(value))
+}
+
+/// Failure while streaming elf totals from a reader
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+    /// An elf's total got too big for the calorie type at this line
+    Overflow { elf: usize, line: usize, line_text: String, calories: &'static str },
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(error) => write!(f, "failed to read input: {}", error),
+            ReadError::Parse(error) => write!(f, "{}", error),
+            ReadError::Overflow { elf, line, calories, .. } => {
+                write!(f, "elf {}'s total overflows {} at line {}", elf, calories, line)
+            },
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> ReadError {
+        ReadError::Io(error)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(error: ParseError) -> ReadError {
+        ReadError::Parse(error)
+    }
+}
+
+impl ReadError {
+    /// The error for input read from memory, which can't fail to read
+    pub fn into_parse_error(self) -> ParseError {
+        match self {
+            ReadError::Parse(error) => error,
+            ReadError::Overflow { elf, line, line_text, calories } => {
+                ParseError::line(Day1::DAY, line, &line_text, format!("a calorie count that keeps elf {}'s total within {}", elf, calories))
+            },
+            ReadError::Io(error) => panic!("Reading from memory failed: {}", error),
+        }
+    }
+}
+
+/// One elf's group of lines
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elf<C = u32> {
+    /// Position in the input, from 1
+    pub index: usize,
+    pub items: usize,
+    pub total: C,
+}
+
+/// Call `visit` with each elf as soon as its group of lines ends
+///
+/// Groups are separated by blank lines, with or without a `\r`. The last group counts even
+/// when no blank line or newline follows it, and runs of blank lines don't make empty elves.
+/// Items and totals are added up in `C`, failing rather than wrapping when they don't fit.
+pub fn for_each_elf<C: Calories, R: BufRead>(reader: R, mut visit: impl FnMut(Elf<C>)) -> Result<(), ReadError> {
+    for (i, paragraph) in read_paragraphs(reader).enumerate() {
+        let paragraph = paragraph?;
+        let mut total = C::default();
+        for (line_number, line) in paragraph.numbered() {
+            let calories = line.parse::<C>()
+                .map_err(|_| ParseError::line(Day1::DAY, line_number, line, format!("an integer calorie count that fits in {}", C::NAME)))?;
+            total = total.checked_add(calories).ok_or_else(|| ReadError::Overflow {
+                elf: i + 1,
+                line: line_number,
+                line_text: line.to_string(),
+                calories: C::NAME,
+            })?;
+        }
+        visit(Elf { index: i + 1, items: paragraph.lines.len(), total });
+    }
+    Ok(())
+}
+
+/// Call `visit` with each elf's total as soon as its group of lines ends, see [`for_each_elf`]
+pub fn for_each_total<C: Calories, R: BufRead>(reader: R, mut visit: impl FnMut(C)) -> Result<(), ReadError> {
+    for_each_elf(reader, |elf| visit(elf.total))
+}
+
+/// The `n` largest values pushed so far, in a min-heap that never holds more than `n`
+pub struct TopN<C = u32> {
+    n: usize,
+    heap: BinaryHeap<Reverse<C>>,
+}
+
+impl<C: Calories> TopN<C> {
+    pub fn new(n: usize) -> TopN<C> {
+        TopN { n, heap: BinaryHeap::with_capacity(n + 1) }
+    }
+
+    pub fn push(&mut self, value: C) {
+        if self.heap.len() < self.n {
+            self.heap.push(Reverse(value));
+        } else if self.heap.peek().is_some_and(|&Reverse(smallest)| value > smallest) {
+            self.heap.pop();
+            self.heap.push(Reverse(value));
+        }
+    }
+
+    /// Largest first
+    pub fn into_sorted_vec(self) -> Vec<C> {
+        // Ascending order of `Reverse` is descending order of the values
+        self.heap.into_sorted_vec().into_iter().map(|Reverse(value)| value).collect()
+    }
+}
+
+/// The `n` largest elf totals in `reader`, largest first, without keeping the others
+pub fn top_totals<C: Calories, R: BufRead>(reader: R, n: usize) -> Result<Vec<C>, ReadError> {
+    let mut top = TopN::new(n);
+    for_each_total(reader, |total| top.push(total))?;
+    Ok(top.into_sorted_vec())
+}
+
+/// Percentiles reported by [`Stats`]
+pub const PERCENTILES: [u32; 5] = [10, 25, 75, 90, 99];
+
+/// Rows in the [`Stats`] histogram
+pub const HISTOGRAM_BUCKETS: usize = 10;
+
+/// Summary of a whole calorie inventory
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats<C = u32> {
+    pub elves: usize,
+    pub items: usize,
+    /// Everything carried, saturating at `u128::MAX` should even that overflow
+    pub calories: u128,
+    /// Largest totals first, earlier elves first among equals
+    pub leaders: Vec<Elf<C>>,
+    pub mean: f64,
+    pub median: f64,
+    /// Nearest-rank percentile of the totals for each of [`PERCENTILES`]
+    pub percentiles: Vec<(u32, C)>,
+    /// Equal-width ranges of totals, inclusive, with the number of elves in each
+    pub histogram: Vec<(u128, u128, usize)>,
+    /// Every elf carrying the largest number of items
+    pub most_items: Vec<Elf<C>>,
+    /// Totals shared by more than one elf, with those elves' indices
+    pub ties: Vec<(C, Vec<usize>)>,
+}
+
+impl<C: Calories> Stats<C> {
+    /// Statistics over `elves` with the top `leaders` of them named, or `None` without elves
+    pub fn new(elves: &[Elf<C>], leaders: usize) -> Option<Stats<C>> {
+        if elves.is_empty() {
+            return None;
+        }
+        let mut ranked = elves.to_vec();
+        ranked.sort_by_key(|elf| (Reverse(elf.total), elf.index));
+        let mut totals: Vec<C> = elves.iter().map(|elf| elf.total).collect();
+        totals.sort();
+        let n = totals.len();
+        let calories = totals.iter().fold(0u128, |sum, total| sum.saturating_add(total.to_u128()));
+        let median = if n % 2 == 1 {
+            totals[n / 2].to_u128() as f64
+        } else {
+            (totals[n / 2 - 1].to_u128() as f64 + totals[n / 2].to_u128() as f64) / 2.0
+        };
+        let percentiles = PERCENTILES
+            .iter()
+            .map(|&p| (p, totals[(p as usize * n).div_ceil(100).max(1) - 1]))
+            .collect();
+
+        let (min, max) = (totals[0].to_u128(), totals[n - 1].to_u128());
+        let width = (max - min) / HISTOGRAM_BUCKETS as u128 + 1;
+        let mut histogram: Vec<(u128, u128, usize)> = (0..HISTOGRAM_BUCKETS as u128)
+            .map(|i| {
+                let high = (i + 1).checked_mul(width).and_then(|end| min.checked_add(end - 1)).map_or(max, |high| high.min(max));
+                (min.saturating_add(i.saturating_mul(width)), high, 0)
+            })
+            .take_while(|&(low, _, _)| low <= max)
+            .collect();
+        let last = histogram.len() - 1;
+        for total in &totals {
+            histogram[(((total.to_u128() - min) / width) as usize).min(last)].2 += 1;
+        }
+
+        let most = elves.iter().map(|elf| elf.items).max().unwrap_or(0);
+        let mut ties: Vec<(C, Vec<usize>)> = Vec::new();
+        for elf in &ranked {
+            match ties.last_mut() {
+                Some((total, indices)) if *total == elf.total => indices.push(elf.index),
+                _ => ties.push((elf.total, vec![elf.index])),
+            }
+        }
+        ties.retain(|(_, indices)| indices.len() > 1);
+
+        Some(Stats {
+            elves: n,
+            items: elves.iter().map(|elf| elf.items).sum(),
+            calories,
+            leaders: ranked.into_iter().take(leaders).collect(),
+            mean: calories as f64 / n as f64,
+            median,
+            percentiles,
+            histogram,
+            most_items: elves.iter().filter(|elf| elf.items == most).copied().collect(),
+            ties,
+        })
+    }
+
+    pub fn to_text(&self) -> String {
+        let indices = |indices: &mut dyn Iterator<Item = usize>| {
+            indices.map(|index| index.to_string()).collect::<Vec<_>>().join(", ")
+        };
+        let mut out = format!("Elves: {}, carrying {} calories in {} items\n", self.elves, self.calories, self.items);
+        out += "Leaders:\n";
+        for (rank, elf) in self.leaders.iter().enumerate() {
+            out += &format!("  {}. elf {}: {} calories in {} items\n", rank + 1, elf.index, elf.total, elf.items);
+        }
+        out += &format!("Mean: {:.2}\nMedian: {:.1}\nPercentiles:", self.mean, self.median);
+        for (p, value) in &self.percentiles {
+            out += &format!(" p{}={}", p, value);
+        }
+        out += "\nHistogram:\n";
+        let widest = self.histogram.iter().map(|&(_, high, _)| high.to_string().len()).max().unwrap_or(0);
+        let tallest = self.histogram.iter().map(|&(_, _, count)| count).max().unwrap_or(1);
+        for &(low, high, count) in &self.histogram {
+            let bar = "#".repeat((count * 40).div_ceil(tallest));
+            out += &format!("  {:>w$}-{:<w$} | {} {}\n", low, high, bar, count, w = widest);
+        }
+        let most = self.most_items.first().map_or(0, |elf| elf.items);
+        out += &format!("Most items: {} by elf {}\n", most, indices(&mut self.most_items.iter().map(|elf| elf.index)));
+        if self.ties.is_empty() {
+            out += "Ties: none\n";
+        } else {
+            out += "Ties:\n";
+            for (total, elves) in &self.ties {
+                out += &format!("  {} calories: elves {}\n", total, indices(&mut elves.iter().copied()));
+            }
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let elf = |elf: &Elf<C>| format!("{{\"index\":{},\"items\":{},\"total\":{}}}", elf.index, elf.items, elf.total);
+        let list = |items: Vec<String>| format!("[{}]", items.join(","));
+        let percentiles: Vec<String> = self
+            .percentiles
+            .iter()
+            .map(|(p, value)| format!("{}:{}", json_string(&format!("p{}", p)), value))
+            .collect();
+        let histogram = self
+            .histogram
+            .iter()
+            .map(|(low, high, count)| format!("{{\"low\":{},\"high\":{},\"elves\":{}}}", low, high, count))
+            .collect();
+        let ties = self
+            .ties
+            .iter()
+            .map(|(total, elves)| {
+                let elves = elves.iter().map(|index| index.to_string()).collect();
+                format!("{{\"total\":{},\"elves\":{}}}", total, list(elves))
+            })
+            .collect();
+        format!(
+            "{{\"elves\":{},\"items\":{},\"calories\":{},\"leaders\":{},\"mean\":{:.2},\"median\":{:.1},\"percentiles\":{{{}}},\"histogram\":{},\"most_items\":{},\"ties\":{}}}",
+            self.elves,
+            self.items,
+            self.calories,
+            list(self.leaders.iter().map(elf).collect()),
+            self.mean,
+            self.median,
+            percentiles.join(","),
+            list(histogram),
+            list(self.most_items.iter().map(elf).collect()),
+            list(ties),
+        )
+    }
+}
+
+/// Number of `k`-elf combinations searched exhaustively before [`closest_k`] and
+/// [`fewest_to_reach`] switch to their approximation
+pub const EXACT_LIMIT: u128 = 1_000_000;
+
+/// Improving passes the approximation makes at most
+const MAX_PASSES: usize = 100;
+
+/// Elves picked by a selection query
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selection {
+    /// Indices of the elves, ascending
+    pub elves: Vec<usize>,
+    pub total: u128,
+    /// Whether no other choice of as many elves is better
+    pub exact: bool,
+}
+
+/// `n` choose `k`, or `None` once it passes `limit`
+fn combinations(n: usize, k: usize, limit: u128) -> Option<u128> {
+    let k = k.min(n - k);
+    let mut count: u128 = 1;
+    for i in 0..k {
+        count = count.checked_mul((n - i) as u128)? / (i + 1) as u128;
+        if count > limit {
+            return None;
+        }
+    }
+    Some(count)
+}
+
+/// Best `k` of `values` (total, elf index) by `cost` of their sum, `None` meaning not allowed
+///
+/// The sum is `None` when it doesn't fit in a `u128`, so such combinations can be skipped
+/// rather than compared by a clamped total.
+///
+/// Tries every combination when there are at most [`EXACT_LIMIT`]. Otherwise it starts from the
+/// best run of `k` consecutive values in sorted order, then keeps swapping one picked value for
+/// the unpicked one nearest the value that would hit the target exactly, as long as that lowers
+/// the cost. That finds a local optimum, which is usually very close but not guaranteed best.
+fn best_k(
+    mut values: Vec<(u128, usize)>,
+    k: usize,
+    target: u128,
+    cost: impl Fn(Option<u128>) -> Option<u128>,
+) -> Option<Selection> {
+    let selection = |picked: &[(u128, usize)], total: u128, exact: bool| {
+        let mut elves: Vec<usize> = picked.iter().map(|&(_, index)| index).collect();
+        elves.sort();
+        Selection { elves, total, exact }
+    };
+    if k > values.len() {
+        return None;
+    }
+    values.sort();
+
+    if combinations(values.len(), k, EXACT_LIMIT).is_some() {
+        let mut positions: Vec<usize> = (0..k).collect();
+        let mut best: Option<(u128, u128, Vec<usize>)> = None;
+        loop {
+            let sum = positions.iter().try_fold(0u128, |sum, &p| sum.checked_add(values[p].0));
+            if let (Some(cost), Some(sum)) = (cost(sum), sum) {
+                if best.as_ref().is_none_or(|(best, _, _)| cost < *best) {
+                    best = Some((cost, sum, positions.clone()));
+                }
+            }
+            // Next combination in lexicographic order
+            let Some(i) = (0..k).rev().find(|&i| positions[i] < values.len() - k + i) else {
+                break;
+            };
+            positions[i] += 1;
+            for j in i + 1..k {
+                positions[j] = positions[j - 1] + 1;
+            }
+        }
+        let (_, sum, positions) = best?;
+        let picked: Vec<(u128, usize)> = positions.iter().map(|&p| values[p]).collect();
+        return Some(selection(&picked, sum, true));
+    }
+
+    // Sums of consecutive runs grow with the start, so the best run is a cheap starting point.
+    // The running sum wraps, counting carries, so it stays exact while passing through overflow.
+    let (mut sum, mut carries) = (0u128, 0i64);
+    for &(value, _) in &values[..k] {
+        let overflow;
+        (sum, overflow) = sum.overflowing_add(value);
+        carries += overflow as i64;
+    }
+    let mut start = None;
+    for first in 0..=values.len() - k {
+        if first > 0 {
+            let (dropped, borrow) = sum.overflowing_sub(values[first - 1].0);
+            let (added, overflow) = dropped.overflowing_add(values[first + k - 1].0);
+            sum = added;
+            carries += overflow as i64 - borrow as i64;
+        }
+        if let Some(cost) = cost(Some(sum).filter(|_| carries == 0)) {
+            if start.is_none_or(|(_, best, _)| cost < best) {
+                start = Some((first, cost, sum));
+            }
+        }
+    }
+    let (first, mut current, mut sum) = start?;
+    let mut unpicked: Vec<(u128, usize)> = values[..first].iter().chain(&values[first + k..]).copied().collect();
+    let mut picked: Vec<(u128, usize)> = values[first..first + k].to_vec();
+    for _ in 0..MAX_PASSES {
+        let mut improved = false;
+        for slot in picked.iter_mut() {
+            if current == 0 {
+                break;
+            }
+            let value = slot.0;
+            let rest = sum - value;
+            let ideal = target.saturating_sub(rest);
+            let at = unpicked.partition_point(|&(candidate, _)| candidate < ideal);
+            let best = [at.checked_sub(1), Some(at)]
+                .into_iter()
+                .flatten()
+                .filter(|&i| i < unpicked.len())
+                .filter_map(|i| Some((cost(rest.checked_add(unpicked[i].0))?, i)))
+                .min();
+            if let Some((new_cost, i)) = best.filter(|&(new_cost, _)| new_cost < current) {
+                let replacement = unpicked.remove(i);
+                let old = std::mem::replace(slot, replacement);
+                let at = unpicked.partition_point(|&candidate| candidate < old);
+                unpicked.insert(at, old);
+                sum = rest + replacement.0;
+                current = new_cost;
+                improved = true;
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+    // Hitting the target exactly can't be beaten
+    Some(selection(&picked, sum, current == 0))
+}
+
+/// `k` elves whose combined calories are as close as possible to `target`
+///
+/// `None` with fewer than `k` elves, or when every choice of `k` adds up to more than a `u128`.
+pub fn closest_k<C: Calories>(elves: &[Elf<C>], k: usize, target: u128) -> Option<Selection> {
+    let values = elves.iter().map(|elf| (elf.total.to_u128(), elf.index)).collect();
+    best_k(values, k, target, |sum| Some(sum?.abs_diff(target)))
+}
+
+/// Fewest elves carrying at least `target` calories together, overshooting it as little as possible
+///
+/// The number of elves is always exact, since the largest totals reach the target soonest. Which
+/// elves of that many overshoot least is exact or approximate like [`closest_k`]. `None` when
+/// all of them together fall short, or when every choice of that many overflows a `u128`.
+pub fn fewest_to_reach<C: Calories>(elves: &[Elf<C>], target: u128) -> Option<Selection> {
+    let mut totals: Vec<u128> = elves.iter().map(|elf| elf.total.to_u128()).collect();
+    totals.sort_by_key(|&total| Reverse(total));
+    let mut sum = 0u128;
+    let count = match totals.iter().position(|&total| {
+        sum = sum.saturating_add(total);
+        sum >= target
+    }) {
+        _ if target == 0 => 0,
+        Some(position) => position + 1,
+        None => return None,
+    };
+    let values = elves.iter().map(|elf| (elf.total.to_u128(), elf.index)).collect();
+    best_k(values, count, target, |sum| sum?.checked_sub(target))
+}
+
+/// What the puzzle needs from an inventory, gathered in one pass without keeping every total
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaders {
+    /// The three largest totals, largest first, or all of them when there are fewer elves
+    pub top: Vec<u32>,
+    pub elves: usize,
+}
+
+#[derive(Clone)]
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+    const EXAMPLES: &'static [Example] = &[
+        Example { input: include_str!("../day 1/test.txt"), part1: Some("24000"), part2: Some("45000") },
+    ];
+
+    type Parsed = Leaders;
+    type Answer1 = u32;
+    /// Wider than a total, so three of them can't overflow
+    type Answer2 = u64;
+
+    fn parse(&self, contents: &str) -> Result<Leaders, ParseError> {
+        let mut top = TopN::new(3);
+        let mut elves = 0;
+        for_each_total(contents.as_bytes(), |total| {
+            top.push(total);
+            elves += 1;
+        })
+        .map_err(ReadError::into_parse_error)?;
+        if elves == 0 {
+            return Err(ParseError::missing(Self::DAY, 1, "", "at least one elf's calories"));
+        }
+        Ok(Leaders { top: top.into_sorted_vec(), elves })
+    }
+
+    fn part1(&self, leaders: &Leaders) -> u32 {
+        leaders.top[0]
+    }
+
+    /// Up to three elves when there are fewer
+    fn part2(&self, leaders: &Leaders) -> u64 {
+        leaders.top.iter().map(|&total| total as u64).sum()
+    }
+
+    fn debug(&self, leaders: &Leaders) -> Vec<(&'static str, String)> {
+        vec![("elves", leaders.elves.to_string())]
+    }
+
+    fn generate(&self, gen: &mut Generator) -> Option<String> {
+        let elves = gen.size(250, 1..=100_000);
+        let max_items = gen.param("items", 15, 1..=1000);
+        // Big enough to overflow a u32 total on purpose, for stress inputs to `day1 --width u64`
+        let max_calories = gen.param("calories", 60_000, 1..=u32::MAX as u64);
+        let mut out = String::new();
+        for elf in 0..elves {
+            if elf > 0 {
+                out.push('\n');
+            }
+            for _ in 0..gen.rng.range(1..=max_items) {
+                out += &format!("{}\n", gen.rng.range(1..=max_calories));
+            }
+        }
+        Some(out)
+    }
+
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        // Elves and their items in a new order, which keeps every total
+        let mut elves: Vec<Vec<&str>> = paragraphs(input).map(|paragraph| paragraph.lines).collect();
+        rng.shuffle(&mut elves);
+        let mut out = String::new();
+        for (i, elf) in elves.iter_mut().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            rng.shuffle(elf);
+            for item in elf.iter() {
+                out += &format!("{}\n", item);
+            }
+        }
+        Some(out)
+    }
+}
+
+#[test]
+fn test_top_totals_streaming() {
+    // CRLF blank lines and no newline after the last elf, who carries the most
+    let input = "1000\r\n2000\r\n\r\n4000\r\n\r\n\r\n5000\r\n6000\r\n\r\n7000\r\n8000";
+    assert_eq!(top_totals::<u32, _>(input.as_bytes(), 3).unwrap(), vec![15000, 11000, 4000]);
+    assert_eq!(top_totals::<u32, _>(input.as_bytes(), 10).unwrap(), vec![15000, 11000, 4000, 3000]);
+    assert!(top_totals::<u32, _>(input.as_bytes(), 0).unwrap().is_empty());
+    assert_eq!(Day1.parse(input).unwrap(), Leaders { top: vec![15000, 11000, 4000], elves: 4 });
+    assert_eq!(Day1.part2(&Day1.parse("5
+
+6
+").unwrap()), 11);
+    match top_totals::<u32, _>("1\n\nx\n".as_bytes(), 1) {
+        Err(ReadError::Parse(error)) => assert_eq!(error.line, 3),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+
+    // Too much for a u32 elf is an error naming the elf, and fits once the totals are wider
+    let big = "1\n\n4294967295\n1\n";
+    match top_totals::<u32, _>(big.as_bytes(), 1) {
+        Err(ReadError::Overflow { elf, line, .. }) => assert_eq!((elf, line), (2, 4)),
+        other => panic!("expected an overflow, got {:?}", other),
+    }
+    assert_eq!(top_totals::<u64, _>(big.as_bytes(), 1).unwrap(), vec![4_294_967_296]);
+    assert_eq!(checked_sum(&[u32::MAX, 1]), None);
+}
+
+#[test]
+fn test_stats() {
+    let mut elves: Vec<Elf> = Vec::new();
+    for_each_elf(Day1::EXAMPLES[0].input.as_bytes(), |elf| elves.push(elf)).unwrap();
+    let stats = Stats::new(&elves, 3).unwrap();
+    assert_eq!((stats.elves, stats.items, stats.calories), (5, 10, 55000));
+    assert_eq!(stats.leaders.iter().map(|elf| elf.index).collect::<Vec<_>>(), vec![4, 3, 5]);
+    assert_eq!((stats.mean, stats.median), (11000.0, 10000.0));
+    assert_eq!(stats.percentiles[0], (10, 4000));
+    assert_eq!(stats.histogram.iter().map(|bucket| bucket.2).sum::<usize>(), 5);
+    assert_eq!(stats.most_items.iter().map(|elf| elf.index).collect::<Vec<_>>(), vec![1, 4]);
+    assert!(stats.ties.is_empty());
+    assert!(stats.to_json().starts_with("{\"elves\":5,\"items\":10,\"calories\":55000,\"leaders\":[{\"index\":4,\"items\":3,\"total\":24000}"));
+
+    let tied: [Elf; 2] = [Elf { index: 1, items: 1, total: 5 }, Elf { index: 2, items: 2, total: 5 }];
+    assert_eq!(Stats::new(&tied, 1).unwrap().ties, vec![(5, vec![1, 2])]);
+    assert!(Stats::<u32>::new(&[], 3).is_none());
+
+    // Totals spanning the whole type, where bucket bounds get close to overflowing
+    let extremes = [Elf { index: 1, items: 1, total: 0 }, Elf { index: 2, items: 1, total: u32::MAX }];
+    let histogram = Stats::new(&extremes, 1).unwrap().histogram;
+    assert_eq!(histogram.len(), HISTOGRAM_BUCKETS);
+    assert_eq!((histogram[0].0, histogram[0].2), (0, 1));
+    assert_eq!(*histogram.last().unwrap(), (9 * 429_496_730, u32::MAX as u128, 1));
+    let extremes = [Elf { index: 1, items: 1, total: 0 }, Elf { index: 2, items: 1, total: u128::MAX }];
+    let histogram = Stats::new(&extremes, 1).unwrap().histogram;
+    assert_eq!(histogram.iter().map(|bucket| bucket.2).sum::<usize>(), 2);
+    assert_eq!((histogram.last().unwrap().1, histogram.last().unwrap().2), (u128::MAX, 1));
+}
+
+#[test]
+fn test_selection_queries() {
+    let mut elves: Vec<Elf> = Vec::new();
+    for_each_elf(Day1::EXAMPLES[0].input.as_bytes(), |elf| elves.push(elf)).unwrap();
+    // Totals by elf: 6000, 4000, 11000, 24000, 10000
+    let closest = closest_k(&elves, 2, 16000).unwrap();
+    assert_eq!((closest.elves, closest.total, closest.exact), (vec![1, 5], 16000, true));
+    assert_eq!(closest_k(&elves, 1, 16000).unwrap().elves, vec![3]);
+    assert_eq!(closest_k(&elves, 3, 20000).unwrap().elves, vec![1, 2, 5]);
+    assert!(closest_k(&elves, 6, 0).is_none());
+    let reach = fewest_to_reach(&elves, 30000).unwrap();
+    assert_eq!((reach.elves, reach.total), (vec![1, 4], 30000));
+    assert!(fewest_to_reach(&elves, 60000).is_none());
+
+    // Too many combinations to try them all, so the approximation runs
+    let many: Vec<Elf> = (1..=200).map(|index| Elf { index, items: 1, total: (index as u32 * 7919) % 1000 }).collect();
+    let closest = closest_k(&many, 20, 12345).unwrap();
+    assert_eq!(closest.elves.len(), 20);
+    assert!(closest.total.abs_diff(12345) <= 1 && closest.exact == (closest.total == 12345), "{:?}", closest);
+    let reach = fewest_to_reach(&many, 50000).unwrap();
+    let mut largest: Vec<u32> = many.iter().map(|elf| elf.total).collect();
+    largest.sort_by_key(|&total| Reverse(total));
+    assert!(largest[..reach.elves.len() - 1].iter().sum::<u32>() < 50000);
+    assert!(reach.total >= 50000 && reach.total < 50000 + 20, "{:?}", reach);
+
+    // Combinations whose total doesn't fit in a u128 are skipped, not clamped to u128::MAX
+    let huge = |totals: &[u128]| -> Vec<Elf<u128>> {
+        totals.iter().enumerate().map(|(i, &total)| Elf { index: i + 1, items: 1, total }).collect()
+    };
+    assert_eq!(closest_k(&huge(&[1, u128::MAX]), 2, 5), None);
+    let closest = closest_k(&huge(&[u128::MAX, 2, u128::MAX - 1, 3]), 2, u128::MAX).unwrap();
+    assert_eq!((closest.elves, closest.total, closest.exact), (vec![2, 4], 5, true));
+    let mut totals: Vec<u128> = (1..=150).collect();
+    totals.extend([u128::MAX / 2; 50]);
+    let closest = closest_k(&huge(&totals), 20, 1000).unwrap();
+    assert!(closest.elves.iter().all(|&index| index <= 150) && closest.total.abs_diff(1000) <= 1, "{:?}", closest);
+}
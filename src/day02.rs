@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use crate::gen::{Generator, Rng};
+use crate::{Example, ParseError, Solution};
+
+pub fn calculate_points(contents_list: &[String], point_mapping: HashMap<&str, i32>) -> i32 {
+    let mut points = 0;
+    for line in contents_list {
+        points += point_mapping.get(line.as_str()).expect("Mapping not found");
+    }
+    points
+}
+
+#[derive(Clone)]
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+    const EXAMPLES: &'static [Example] = &[
+        Example { input: include_str!("../day 2/test.txt"), part1: Some("15"), part2: Some("12") },
+    ];
+
+    /// Strategy guide rounds, e.g. "A Y"
+    type Parsed = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, contents: &str) -> Result<Vec<String>, ParseError> {
+        let mut rounds = Vec::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let mut chars = line.chars();
+            match (chars.next(), chars.next(), chars.next(), chars.next()) {
+                (Some('A'..='C'), Some(' '), Some('X'..='Z'), None) => rounds.push(line.to_string()),
+                _ => return Err(ParseError::line(Self::DAY, i + 1, line, "a round like 'A Y'")),
+            }
+        }
+        Ok(rounds)
+    }
+
+    fn part1(&self, contents_list: &Vec<String>) -> i32 {
+        // A, X: Rock     +1
+        // B, Y: Paper    +2
+        // C, Z: Scissors +3
+        // Win: +6, Draw: +3, Lose: +0
+        //
+        let point_mapping = HashMap::from([
+            ("A X", 4),
+            ("A Y", 8),
+            ("A Z", 3),
+            ("B X", 1),
+            ("B Y", 5),
+            ("B Z", 9),
+            ("C X", 7),
+            ("C Y", 2),
+            ("C Z", 6)
+        ]);
+
+        calculate_points(contents_list, point_mapping)
+    }
+
+    fn part2(&self, contents_list: &Vec<String>) -> i32 {
+        // X: Lose
+        // Y: Draw
+        // Z: Win
+        let point_mapping = HashMap::from([
+            ("A X", 3),
+            ("A Y", 4),
+            ("A Z", 8),
+            ("B X", 1),
+            ("B Y", 5),
+            ("B Z", 9),
+            ("C X", 2),
+            ("C Y", 6),
+            ("C Z", 7)
+        ]);
+
+        calculate_points(contents_list, point_mapping)
+    }
+
+    fn debug(&self, contents_list: &Vec<String>) -> Vec<(&'static str, String)> {
+        vec![("rounds", contents_list.len().to_string())]
+    }
+
+    fn generate(&self, gen: &mut Generator) -> Option<String> {
+        let rounds = gen.size(2500, 1..=1_000_000);
+        let mut out = String::new();
+        for _ in 0..rounds {
+            out += &format!("{} {}\n", gen.rng.choose(&['A', 'B', 'C']), gen.rng.choose(&['X', 'Y', 'Z']));
+        }
+        Some(out)
+    }
+
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut rounds: Vec<&str> = input.lines().filter(|line| !line.is_empty()).collect();
+        rng.shuffle(&mut rounds);
+        Some(rounds.iter().map(|round| format!("{}\n", round)).collect())
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::gen::{Generator, Rng};
+use crate::{Example, ParseError, Solution};
+
+#[derive(Clone)]
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+    const EXAMPLES: &'static [Example] = &[
+        Example { input: include_str!("../day 3/test.txt"), part1: Some("157"), part2: Some("70") },
+    ];
+
+    /// One rucksack per line
+    type Parsed = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, contents: &str) -> Result<Vec<String>, ParseError> {
+        let mut rucksacks = Vec::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            if let Some((offset, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                let item = &line[offset..offset + c.len_utf8()];
+                return Err(ParseError::token(Self::DAY, i + 1, line, item, "an item letter a-z or A-Z"));
+            }
+            if line.len() % 2 != 0 {
+                return Err(ParseError::line(Self::DAY, i + 1, line, "an even number of items"));
+            }
+            rucksacks.push(line.to_string());
+        }
+        Ok(rucksacks)
+    }
+
+    fn part1(&self, contents_list: &Vec<String>) -> i32 {
+        let values = item_values();
+        let mut total = 0;
+        for line in contents_list {
+            let rucksack: Vec<char> = line.chars().collect();
+            // Separate into compartments
+            let first_compartment: HashSet<char> = HashSet::from_iter(rucksack[..rucksack.len() / 2].iter().cloned());
+            let second_compartment: HashSet<char> = HashSet::from_iter(rucksack[rucksack.len() / 2..].iter().cloned());
+            // Find intersection
+            let items = first_compartment.intersection(&second_compartment);
+            let mut num_items = 0;
+            // Iterate through all items (should only contain 1)
+            for item in items {
+                total += values.get(item).unwrap_or_else(|| panic!("Found invalid item {}", item));
+                num_items += 1;
+            }
+            if num_items != 1 {
+                crate::warn!("compartments should share exactly one item", rucksack = line, shared = num_items);
+                crate::debug!("compartments", first = first_compartment, second = second_compartment);
+            }
+        }
+        total
+    }
+
+    fn part2(&self, contents_list: &Vec<String>) -> i32 {
+        let values = item_values();
+        let mut total = 0;
+        let mut elves: [HashSet<char>; 3] = [HashSet::new(), HashSet::new(), HashSet::new()];
+        for (i, line) in contents_list.iter().enumerate() {
+            // Rolling overwrite for groups
+            elves[i % 3] = HashSet::from_iter(line.chars().collect::<Vec<char>>().iter().cloned());
+            if i % 3 == 2 {
+                // Calculate elf group item every 3 lines
+                let mut iter = elves.iter();
+                // Initialize items to first elf
+                let mut items = iter.next().unwrap().clone();
+                // Sequentially intersect with other elves
+                for elf in iter {
+                    items = items.intersection(elf).cloned().collect();
+                }
+                let mut num_items = 0;
+                for item in items {
+                    total += values.get(&item).unwrap_or_else(|| panic!("Found invalid item {}", item));
+                    num_items += 1;
+                }
+                if num_items != 1 {
+                    crate::warn!("group should share exactly one item", group = i / 3 + 1, shared = num_items);
+                }
+            }
+        }
+        total
+    }
+
+    fn debug(&self, contents_list: &Vec<String>) -> Vec<(&'static str, String)> {
+        vec![("rucksacks", contents_list.len().to_string())]
+    }
+
+    fn generate(&self, gen: &mut Generator) -> Option<String> {
+        // Rounded down to whole groups of three elves
+        let groups = gen.size(300, 3..=300_000) / 3;
+        let max_half = gen.param("half", 16, 2..=1000);
+        let letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        let mut out = String::new();
+        for _ in 0..groups {
+            let mut pool = letters.clone();
+            gen.rng.shuffle(&mut pool);
+            let badge = pool.pop().unwrap();
+            // Each elf of the group draws from its own 17 letters plus the badge, so the badge is all they share
+            for own in pool.chunks(17) {
+                let mut available = own.to_vec();
+                available.push(badge);
+                let shared = available.remove(gen.rng.index(available.len()));
+                available.retain(|&c| c != badge);
+                let (left_pool, right_pool) = available.split_at(available.len() / 2);
+                let half = gen.rng.range(2..=max_half) as usize;
+                let mut left = vec![shared];
+                if shared != badge {
+                    left.push(badge);
+                }
+                while left.len() < half {
+                    left.push(*gen.rng.choose(left_pool));
+                }
+                let mut right = vec![shared];
+                while right.len() < half {
+                    right.push(*gen.rng.choose(right_pool));
+                }
+                gen.rng.shuffle(&mut left);
+                gen.rng.shuffle(&mut right);
+                out.extend(left.into_iter().chain(right));
+                out.push('\n');
+            }
+        }
+        Some(out)
+    }
+
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let rucksacks: Vec<&str> = input.lines().filter(|line| !line.is_empty()).collect();
+        let mut groups: Vec<&[&str]> = rucksacks.chunks(3).collect();
+        rng.shuffle(&mut groups);
+        let mut out = String::new();
+        for group in groups {
+            // Relabel the group consistently, except for its badge and each rucksack's shared
+            // item, so the priorities both parts add up stay the same
+            let mut fixed: HashSet<char> = HashSet::new();
+            for rucksack in group {
+                let (first, second) = rucksack.split_at(rucksack.len() / 2);
+                fixed.extend(first.chars().filter(|&c| second.contains(c)));
+            }
+            fixed.extend(group[0].chars().filter(|&c| group[1..].iter().all(|rucksack| rucksack.contains(c))));
+            let free: Vec<char> = ('a'..='z').chain('A'..='Z').filter(|c| !fixed.contains(c)).collect();
+            let mut labels = free.clone();
+            rng.shuffle(&mut labels);
+            let relabel: HashMap<char, char> = free.into_iter().zip(labels).collect();
+            for rucksack in group {
+                out.extend(rucksack.chars().map(|c| relabel.get(&c).copied().unwrap_or(c)));
+                out.push('\n');
+            }
+        }
+        Some(out)
+    }
+}
+
+/// Priority of each item type
+pub fn item_values() -> HashMap<char, i32> {
+    HashMap::from([
+        ('a', 1),
+        ('b', 2),
+        ('c', 3),
+        ('d', 4),
+        ('e', 5),
+        ('f', 6),
+        ('g', 7),
+        ('h', 8),
+        ('i', 9),
+        ('j', 10),
+        ('k', 11),
+        ('l', 12),
+        ('m', 13),
+        ('n', 14),
+        ('o', 15),
+        ('p', 16),
+        ('q', 17),
+        ('r', 18),
+        ('s', 19),
+        ('t', 20),
+        ('u', 21),
+        ('v', 22),
+        ('w', 23),
+        ('x', 24),
+        ('y', 25),
+        ('z', 26),
+        ('A', 27),
+        ('B', 28),
+        ('C', 29),
+        ('D', 30),
+        ('E', 31),
+        ('F', 32),
+        ('G', 33),
+        ('H', 34),
+        ('I', 35),
+        ('J', 36),
+        ('K', 37),
+        ('L', 38),
+        ('M', 39),
+        ('N', 40),
+        ('O', 41),
+        ('P', 42),
+        ('Q', 43),
+        ('R', 44),
+        ('S', 45),
+        ('T', 46),
+        ('U', 47),
+        ('V', 48),
+        ('W', 49),
+        ('X', 50),
+        ('Y', 51),
+        ('Z', 52),
+    ])
+}
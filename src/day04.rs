@@ -0,0 +1,124 @@
+use crate::gen::{Generator, Rng};
+use crate::{Example, ParseError, Solution};
+
+#[derive(Debug)]
+pub struct Assignment {
+    pub low: u32,
+    pub high: u32,
+}
+
+#[derive(Debug)]
+pub struct Pair {
+    pub first: Assignment,
+    pub second: Assignment,
+}
+
+impl Pair {
+    pub fn new(first: Assignment, second: Assignment) -> Pair {
+        Pair { first, second }
+    }
+
+    pub fn is_contained(&self) -> bool {
+        (self.first.low <= self.second.low && self.second.high <= self.first.high )||
+        (self.second.low <= self.first.low && self.first.high <= self.second.high)
+    }
+
+    pub fn is_overlapped(&self) -> bool {
+        (self.first.low <= self.second.low && self.second.low <= self.first.high) ||
+        (self.second.low <= self.first.low && self.first.low <= self.second.high)
+    }
+}
+
+/// Parse a section range such as "2-4" found within `line`
+fn parse_assignment(line_number: usize, line: &str, range: &str) -> Result<Assignment, ParseError> {
+    let (low, high) = range.split_once('-')
+        .ok_or_else(|| ParseError::token(Day4::DAY, line_number, line, range, "a section range like '2-4'"))?;
+    let section = |s: &str| s.parse::<u32>()
+        .map_err(|_| ParseError::token(Day4::DAY, line_number, line, s, "a section number"));
+    Ok(Assignment {
+        low: section(low)?,
+        high: section(high)?,
+    })
+}
+
+#[derive(Clone)]
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+    const EXAMPLES: &'static [Example] = &[
+        Example { input: include_str!("../day 4/test.txt"), part1: Some("2"), part2: Some("4") },
+    ];
+
+    type Parsed = Vec<Pair>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, contents: &str) -> Result<Vec<Pair>, ParseError> {
+        let mut pairs: Vec<Pair> = Vec::new();
+        for (i, line) in contents.lines().enumerate() {
+            let (first, second) = line.split_once(',')
+                .ok_or_else(|| ParseError::line(Self::DAY, i + 1, line, "two comma separated ranges"))?;
+            let first_assignment = parse_assignment(i + 1, line, first)?;
+            let second_assignment = parse_assignment(i + 1, line, second)?;
+            pairs.push(Pair::new(first_assignment, second_assignment));
+        }
+        Ok(pairs)
+    }
+
+    fn part1(&self, pairs: &Vec<Pair>) -> usize {
+        pairs.iter().filter(|&pair| {
+            pair.is_contained()
+        }).count()
+    }
+
+    fn part2(&self, pairs: &Vec<Pair>) -> usize {
+        pairs.iter().filter(|&pair| {
+            pair.is_overlapped()
+        }).count()
+    }
+
+    fn debug(&self, pairs: &Vec<Pair>) -> Vec<(&'static str, String)> {
+        vec![("pairs", pairs.len().to_string())]
+    }
+
+    fn generate(&self, gen: &mut Generator) -> Option<String> {
+        let pairs = gen.size(1000, 1..=1_000_000);
+        let max_section = gen.param("sections", 99, 1..=1_000_000);
+        let mut out = String::new();
+        for _ in 0..pairs {
+            let mut assignment = || {
+                let low = gen.rng.range(1..=max_section);
+                format!("{}-{}", low, gen.rng.range(low..=max_section))
+            };
+            let first = assignment();
+            out += &format!("{},{}\n", first, assignment());
+        }
+        Some(out)
+    }
+
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        let mut pairs = Vec::new();
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let numbers: Vec<u32> = line.split(['-', ',']).map(|n| n.trim().parse().ok()).collect::<Option<_>>()?;
+            pairs.push(numbers);
+        }
+        // Renumber sections with a random increasing map, which keeps every comparison
+        let mut used: Vec<u32> = pairs.iter().flatten().copied().collect();
+        used.sort_unstable();
+        used.dedup();
+        let mut available: Vec<u32> = (1..=(used.len() as u32).max(99)).collect();
+        rng.shuffle(&mut available);
+        let mut renumbered = available[..used.len()].to_vec();
+        renumbered.sort_unstable();
+        let renumber = |n: &u32| renumbered[used.binary_search(n).unwrap()];
+        rng.shuffle(&mut pairs);
+        let mut out = String::new();
+        for pair in pairs {
+            let pair: Vec<u32> = pair.iter().map(renumber).collect();
+            out += &format!("{}-{},{}-{}\n", pair[0], pair[1], pair[2], pair[3]);
+        }
+        Some(out)
+    }
+}
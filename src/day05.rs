@@ -0,0 +1,301 @@
+use crate::gen::{Generator, Rng};
+use crate::paragraph::paragraphs;
+use crate::{Answer, Example, Param, ParseError, Solution};
+
+#[derive(Debug)]
+pub struct Command {
+    pub count: u32,
+    pub from: u32,
+    pub to: u32,
+}
+
+impl Command {
+    pub fn apply(&self, stacks: &mut [Vec<char>]) {
+        for _ in 0..self.count {
+            let item = stacks[self.from as usize].pop().unwrap();
+            stacks[self.to as usize].push(item);
+        }
+    }
+
+    pub fn apply_better(&self, stacks: &mut [Vec<char>]) {
+        let mut items = stacks[self.from as usize].drain((stacks[self.from as usize].len() - self.count as usize)..).collect::<Vec<char>>();
+        stacks[self.to as usize].append(& mut items);
+
+        // let mut items = Vec::new();
+        // for _ in 0..self.count {
+        //     items.push(stacks[self.from as usize].pop().unwrap())
+        // }
+        // items.reverse();
+        // for &item in items.iter() {
+        //     stacks[self.to as usize].push(item);
+        // }
+    }
+}
+
+
+/// Parse the crate drawing into stacks, bottom of each stack first
+///
+/// `lines` are the drawing rows followed by the row of stack numbers.
+fn parse_drawing(lines: &[(usize, &str)]) -> Result<Vec<Vec<char>>, ParseError> {
+    let (&(label_number, labels), rows) = match lines.split_last() {
+        Some(split) => split,
+        None => return Err(ParseError::missing(Day5::DAY, 1, "", "a drawing of the crate stacks")),
+    };
+    let mut num_stacks = 0;
+    for (offset, label) in labels.char_indices().filter(|(_, c)| *c != ' ') {
+        let expected = (num_stacks + 1).to_string();
+        let token = &labels[offset..offset + label.len_utf8()];
+        if offset != 4 * num_stacks + 1 || token != expected {
+            return Err(ParseError::token(Day5::DAY, label_number, labels, token, format!("stack number {}", expected)));
+        }
+        num_stacks += 1;
+    }
+    if num_stacks == 0 {
+        return Err(ParseError::line(Day5::DAY, label_number, labels, "a row of stack numbers"));
+    }
+    let mut stacks = vec![Vec::new(); num_stacks];
+    // Read rows from the bottom up so each stack ends with its top crate
+    for &(line_number, row) in rows.iter().rev() {
+        if let Some((offset, c)) = row.char_indices().find(|(_, c)| !c.is_ascii()) {
+            let token = &row[offset..offset + c.len_utf8()];
+            return Err(ParseError::token(Day5::DAY, line_number, row, token, "a crate like '[A]' or blank space"));
+        }
+        if row.len() > 4 * num_stacks - 1 {
+            let extra = &row[4 * num_stacks - 1..];
+            return Err(ParseError::token(Day5::DAY, line_number, row, extra, "end of line"));
+        }
+        for (k, stack) in stacks.iter_mut().enumerate() {
+            let cell = row.get(4 * k..(4 * k + 3).min(row.len())).unwrap_or("");
+            let error = || ParseError::token(Day5::DAY, line_number, row, cell, "a crate like '[A]' or blank space");
+            match cell.as_bytes() {
+                [b'[', c, b']'] if c.is_ascii_uppercase() => stack.push(*c as char),
+                cell if cell.iter().all(|&c| c == b' ') => {},
+                _ => return Err(error()),
+            }
+        }
+    }
+    Ok(stacks)
+}
+
+/// Crate stacks together with the rearrangement procedure
+#[derive(Debug)]
+pub struct Procedure {
+    pub stacks: Vec<Vec<char>>,
+    pub moves: Vec<Command>,
+}
+
+/// Top crate of each stack, skipping empty stacks
+pub fn stack_tops(stacks: &[Vec<char>]) -> String {
+    stacks.iter().filter_map(|stack| stack.last()).collect()
+}
+
+/// Tops of the stacks after running every move with the given CrateMover model
+fn rearrange(procedure: &Procedure, crane: u32) -> String {
+    let mut stacks = procedure.stacks.clone();
+    for m in procedure.moves.iter() {
+        if crane == 9001 {
+            m.apply_better(&mut stacks[..]);
+        } else {
+            m.apply(&mut stacks[..]);
+        }
+    }
+    stack_tops(&stacks)
+}
+
+/// Parse a line such as "move 3 from 1 to 2" into a zero-indexed command
+///
+/// `heights` are the stack heights before the move and are updated by it, so a move taking more
+/// crates than its stack holds is an error here rather than a panic when the moves are run.
+fn parse_command(line_number: usize, line: &str, heights: &mut [usize]) -> Result<Command, ParseError> {
+    let num_stacks = heights.len();
+    let error = |token: &str, expected: &str| ParseError::token(Day5::DAY, line_number, line, token, expected);
+    let mut parts = line.split(' ');
+    let mut number = |keyword: &str| -> Result<(u32, &str), ParseError> {
+        match parts.next() {
+            Some(k) if k == keyword => {},
+            Some(k) => return Err(error(k, &format!("'{}'", keyword))),
+            None => return Err(ParseError::missing(Day5::DAY, line_number, line, format!("'{}'", keyword))),
+        }
+        match parts.next() {
+            Some(n) => n.parse::<u32>().map(|value| (value, n)).map_err(|_| error(n, "a number")),
+            None => Err(ParseError::missing(Day5::DAY, line_number, line, "a number")),
+        }
+    };
+    let (count, count_token) = number("move")?;
+    let (from, from_token) = number("from")?;
+    let (to, to_token) = number("to")?;
+    if let Some(extra) = parts.next() {
+        return Err(error(extra, "end of line"));
+    }
+    let stack_range = format!("a stack number from 1 to {}", num_stacks);
+    for (stack, token) in [(from, from_token), (to, to_token)] {
+        if stack == 0 || stack as usize > num_stacks {
+            return Err(error(token, &stack_range));
+        }
+    }
+    let (from, to) = (from as usize - 1, to as usize - 1);
+    if count as usize > heights[from] {
+        return Err(error(count_token, &format!("at most {} crates, the height of stack {}", heights[from], from + 1)));
+    }
+    heights[from] -= count as usize;
+    heights[to] += count as usize;
+    Ok(Command { count, from: from as u32, to: to as u32 })
+}
+
+/// CrateMover model used by each part: the 9000 moves one crate at a time, the 9001 several at once
+#[derive(Clone, Debug)]
+pub struct Day5 {
+    pub part1_crane: u32,
+    pub part2_crane: u32,
+}
+
+impl Day5 {
+    pub const DEFAULT: Day5 = Day5 { part1_crane: 9000, part2_crane: 9001 };
+}
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+    const PARAMS: &'static [Param] = &[
+        Param { name: "part1_crane", default: Day5::DEFAULT.part1_crane as i64, min: 9000, max: 9001 },
+        Param { name: "part2_crane", default: Day5::DEFAULT.part2_crane as i64, min: 9000, max: 9001 },
+    ];
+    const EXAMPLES: &'static [Example] = &[
+        Example { input: include_str!("../day 5/test.txt"), part1: Some("CMZ"), part2: Some("MCD") },
+    ];
+
+    type Parsed = Procedure;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(&self, content: &str) -> Result<Procedure, ParseError> {
+        // Drawing is the first paragraph, the moves are everything after it
+        let mut paragraphs = paragraphs(content);
+        let drawing = paragraphs.next();
+        let drawing_end = drawing.as_ref().map_or(0, |drawing| drawing.last_line());
+        if content.lines().nth(drawing_end).is_none() {
+            let line_number = content.lines().count() + 1;
+            return Err(ParseError::missing(Self::DAY, line_number, "", "a blank line before the moves"));
+        }
+        let drawing: Vec<(usize, &str)> = drawing.iter().flat_map(|drawing| drawing.numbered()).collect();
+        let stacks = parse_drawing(&drawing)?;
+        let mut moves = Vec::new();
+        let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+        // Process commands
+        for paragraph in paragraphs {
+            for (line_number, line) in paragraph.numbered() {
+                moves.push(parse_command(line_number, line, &mut heights)?);
+            }
+        }
+        crate::trace!("parsed moves", stacks = stacks, moves = moves);
+        Ok(Procedure { stacks, moves })
+    }
+
+    fn part1(&self, procedure: &Procedure) -> String {
+        rearrange(procedure, self.part1_crane)
+    }
+
+    fn part2(&self, procedure: &Procedure) -> String {
+        rearrange(procedure, self.part2_crane)
+    }
+
+    fn debug(&self, procedure: &Procedure) -> Vec<(&'static str, String)> {
+        vec![
+            ("stacks", procedure.stacks.len().to_string()),
+            ("moves", procedure.moves.len().to_string()),
+            ("initial_tops", stack_tops(&procedure.stacks)),
+        ]
+    }
+
+    fn configure(&mut self, param: &dyn Fn(&str) -> i64) {
+        self.part1_crane = param("part1_crane") as u32;
+        self.part2_crane = param("part2_crane") as u32;
+    }
+
+    fn generate(&self, gen: &mut Generator) -> Option<String> {
+        let num_moves = gen.size(500, 0..=100_000);
+        // Stack numbers are single digits in the drawing
+        let num_stacks = gen.param("stacks", 9, 1..=9) as usize;
+        let height = gen.param("height", 8, 1..=50);
+        let mut stacks: Vec<Vec<char>> = Vec::new();
+        for _ in 0..num_stacks {
+            let crates = gen.rng.range(1..=height);
+            stacks.push((0..crates).map(|_| (b'A' + gen.rng.range(0..=25) as u8) as char).collect());
+        }
+        let tallest = stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut out = String::new();
+        for level in (0..tallest).rev() {
+            let cells: Vec<String> = stacks
+                .iter()
+                .map(|stack| stack.get(level).map_or("   ".to_string(), |c| format!("[{}]", c)))
+                .collect();
+            out += &cells.join(" ");
+            out.push('\n');
+        }
+        let labels: Vec<String> = (1..=num_stacks).map(|n| format!(" {} ", n)).collect();
+        out += &labels.join(" ");
+        out += "\n\n";
+        // Only the heights matter for legal moves; never empty a stack so every part has a top crate
+        let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+        for _ in 0..num_moves {
+            let sources: Vec<usize> = (0..num_stacks).filter(|&i| heights[i] >= 2).collect();
+            if num_stacks < 2 || sources.is_empty() {
+                break;
+            }
+            let from = *gen.rng.choose(&sources);
+            let mut to = gen.rng.index(num_stacks - 1);
+            if to >= from {
+                to += 1;
+            }
+            let count = gen.rng.range(1..=heights[from] as u64 - 1) as usize;
+            heights[from] -= count;
+            heights[to] += count;
+            out += &format!("move {} from {} to {}\n", count, from + 1, to + 1);
+        }
+        Some(out)
+    }
+
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        // Crate letters go through a random permutation, so the answers change by the same permutation
+        let mut letters: Vec<char> = ('A'..='Z').collect();
+        rng.shuffle(&mut letters);
+        let mut out = String::new();
+        let mut in_drawing = true;
+        for line in input.lines() {
+            if line.is_empty() {
+                in_drawing = false;
+            }
+            if in_drawing {
+                out.extend(line.chars().map(|c| if c.is_ascii_uppercase() { letters[(c as u8 - b'A') as usize] } else { c }));
+            } else {
+                out += line;
+            }
+            out.push('\n');
+        }
+        Some(out)
+    }
+
+    fn relabel_answer(&self, input: &str, anonymized: &str, answer: Answer) -> Answer {
+        // The drawings line up character for character, so they spell out the permutation
+        let mut letters: Vec<char> = ('A'..='Z').collect();
+        for (original, relabelled) in input.lines().zip(anonymized.lines()).take_while(|(line, _)| !line.is_empty()) {
+            for (a, b) in original.chars().zip(relabelled.chars()).filter(|(a, _)| a.is_ascii_uppercase()) {
+                letters[(a as u8 - b'A') as usize] = b;
+            }
+        }
+        match answer {
+            Answer::Text(text) => Answer::Text(text.chars().map(|c| if c.is_ascii_uppercase() { letters[(c as u8 - b'A') as usize] } else { c }).collect()),
+            answer => answer,
+        }
+    }
+}
+
+#[test]
+fn test_move_from_short_stack_is_parse_error() {
+    let error = Day5::DEFAULT.parse("[A]    \n 1   2 \n\nmove 1 from 1 to 2\nmove 2 from 2 to 1\n").unwrap_err();
+    assert_eq!((error.line, error.found.as_str()), (5, "2"));
+    assert!(error.expected.contains("at most 1 crates"), "{}", error.expected);
+    // Emptying a stack is fine, it just has no top
+    let procedure = Day5::DEFAULT.parse("[A]    \n 1   2 \n\nmove 1 from 1 to 2\n").unwrap();
+    assert_eq!(rearrange(&procedure, 9000), "A");
+}
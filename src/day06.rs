@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use crate::gen::{Generator, Rng, all_distinct};
+use crate::{Example, ParseError, Solution};
+
+#[derive(Clone)]
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+    const EXAMPLES: &'static [Example] = &[
+        Example { input: include_str!("../day 6/test.txt"), part1: Some("7"), part2: Some("19") },
+        Example { input: "bvwbjplbgvbhsrlpgdmjqwftvncz", part1: Some("5"), part2: Some("23") },
+        Example { input: "nppdvjthqldpwncqszvftbrmjlhg", part1: Some("6"), part2: Some("23") },
+        Example { input: "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", part1: Some("10"), part2: Some("29") },
+        Example { input: "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", part1: Some("11"), part2: Some("26") },
+    ];
+
+    /// Datastream buffer
+    type Parsed = Vec<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, content: &str) -> Result<Vec<char>, ParseError> {
+        let mut lines = content.lines().enumerate();
+        let signal = lines.next().map_or("", |(_, line)| line);
+        if let Some((offset, c)) = signal.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            let letter = &signal[offset..offset + c.len_utf8()];
+            return Err(ParseError::token(Self::DAY, 1, signal, letter, "a lowercase letter"));
+        }
+        if signal.len() < 14 {
+            return Err(ParseError::missing(Self::DAY, 1, signal, "a signal of at least 14 letters"));
+        }
+        if let Some((i, line)) = lines.find(|(_, line)| !line.is_empty()) {
+            return Err(ParseError::line(Self::DAY, i + 1, line, "the signal on a single line"));
+        }
+        Ok(signal.chars().collect())
+    }
+
+    fn part1(&self, chars: &Vec<char>) -> usize {
+        let mut letters = HashMap::new();
+        letters.insert(chars[0], 1);
+        *letters.entry(chars[1]).or_insert(0) += 1;
+        *letters.entry(chars[2]).or_insert(0) += 1;
+        *letters.entry(chars[3]).or_insert(0) += 1;
+        for (i, &letter) in chars[4..].iter().enumerate() {
+            letters.retain(|_, v| *v > 0);
+            if letters.keys().len() == 4 {
+                crate::debug!("start-of-packet marker", index = i + 4, letters = letters);
+                return i + 4;
+            }
+            if letter.is_alphabetic() {
+                *letters.entry(letter).or_insert(0) += 1;
+                *letters.entry(chars[i]).or_insert(0) -= 1;
+            }
+        }
+        panic!("No start-of-packet marker found");
+    }
+
+    fn part2(&self, chars: &Vec<char>) -> usize {
+        // let chars: Vec<char> = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".chars().collect();
+        let mut letters = chars[..14].iter().fold(HashMap::new(), |mut acc, &letter| {
+            *acc.entry(letter).or_insert(0) += 1;
+            acc
+        });
+        for (i, &letter) in chars[14..].iter().enumerate() {
+            letters.retain(|_, v| *v > 0);
+            if letters.keys().len() == 14 {
+                crate::debug!("start-of-message marker", index = i + 14, letters = letters);
+                return i + 14;
+            }
+            if letter.is_alphabetic() {
+                *letters.entry(letter).or_insert(0) += 1;
+                *letters.entry(chars[i]).or_insert(0) -= 1;
+            }
+        }
+        panic!("No start-of-message marker found");
+    }
+
+    fn debug(&self, chars: &Vec<char>) -> Vec<(&'static str, String)> {
+        vec![("signal_length", chars.len().to_string())]
+    }
+
+    fn generate(&self, gen: &mut Generator) -> Option<String> {
+        let marker = gen.param("marker", 1000, 4..=10_000_000) as usize;
+        let message = gen.param("message", marker as u64 + 2000, 0..=10_000_000) as usize;
+        if message < marker + 15 {
+            gen.problem(format!("message must be at least marker + 15 ({}), got {}", marker + 15, message));
+            return Some(String::new());
+        }
+        let length = (gen.size(4096, 1..=10_000_000) as usize).max(message);
+        let letters: Vec<u8> = (b'a'..=b'z').collect();
+        // Append a random letter, repeating the previous one instead if it would complete a
+        // window of `width` distinct letters
+        let push_avoiding = |signal: &mut Vec<u8>, gen: &mut Generator, width: usize| {
+            let mut c = *gen.rng.choose(&letters);
+            if signal.len() + 1 >= width {
+                let mut window = signal[signal.len() + 1 - width..].to_vec();
+                window.push(c);
+                if all_distinct(&window) {
+                    c = *signal.last().unwrap();
+                }
+            }
+            signal.push(c);
+        };
+        // `width` distinct letters ending at `end`, after a repeat of the previous letter so
+        // no earlier window is distinct
+        let push_marker = |signal: &mut Vec<u8>, gen: &mut Generator, width: usize, end: usize| {
+            let first = if end > width { signal.last().copied().unwrap_or(b'a') } else { *gen.rng.choose(&letters) };
+            let mut rest: Vec<u8> = letters.iter().copied().filter(|&c| c != first).collect();
+            gen.rng.shuffle(&mut rest);
+            if end > width {
+                signal.push(first);
+            }
+            signal.push(first);
+            signal.extend_from_slice(&rest[..width - 1]);
+        };
+        let mut signal = Vec::with_capacity(length);
+        while signal.len() + 5 < marker {
+            push_avoiding(&mut signal, gen, 4);
+        }
+        push_marker(&mut signal, gen, 4, marker);
+        while signal.len() + 15 < message {
+            push_avoiding(&mut signal, gen, 14);
+        }
+        push_marker(&mut signal, gen, 14, message);
+        while signal.len() < length {
+            signal.push(*gen.rng.choose(&letters));
+        }
+        Some(format!("{}\n", String::from_utf8(signal).unwrap()))
+    }
+
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        // Any permutation of the letters keeps which windows are all different
+        let mut letters: Vec<char> = ('a'..='z').collect();
+        rng.shuffle(&mut letters);
+        let signal = input.lines().next()?;
+        let mut out: String = signal.chars().map(|c| if c.is_ascii_lowercase() { letters[(c as u8 - b'a') as usize] } else { c }).collect();
+        out.push('\n');
+        Some(out)
+    }
+}
@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+use std::boxed::Box;
+
+use crate::gen::{Generator, Rng};
+use crate::{Example, Param, ParseError, Solution};
+
+// Different type of lines
+pub enum LineTypes {
+    Command(Command), // Command, args
+    Directory(String), // Directory name
+    File(u32, String), // File size, file name
+}
+
+pub enum Command {
+    Cd(String),
+    Ls,
+}
+
+// File system
+/*
+    File system owns the directories
+    Directory owns the files
+*/
+
+#[derive(Clone, Debug)]
+pub struct FileSystem {
+    pub directories: HashMap<String, Box<Directory>>,
+    pub directory_sizes: HashMap<String, u32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Directory {
+    pub name: String,
+    pub full_path: String,
+    pub files: Vec<File>,
+    pub child_directories: Vec<String>,
+    pub parent_directory: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct File {
+    pub name: String,
+    pub size: u32,
+}
+
+impl FileSystem {
+    /// Create a new file system from root
+    ///
+    pub fn new() -> FileSystem {
+        let mut fs = FileSystem {
+            directories: HashMap::new(),
+            directory_sizes: HashMap::new(),
+        };
+
+        fs.directories.insert(String::from("/"), Box::new(Directory::new_root()));
+
+        fs
+    }
+
+    pub fn mkdir(&mut self, dir: Directory) {
+        crate::trace!("creating directory", path = dir.full_path);
+        let parent_dir = self.directories.get_mut(&dir.parent_directory.clone().unwrap()).unwrap();
+        parent_dir.child_directories.push(dir.full_path.clone());
+        self.directories.insert(dir.full_path.clone(), Box::new(dir));
+    }
+
+    pub fn mkfile(&mut self, dir_full_path: String, file: File) {
+        crate::trace!("creating file", name = file.name, directory = dir_full_path);
+        let dir = self.directories.get_mut(&dir_full_path).unwrap_or_else(|| panic!("Invalid directory {}", dir_full_path.as_str()));
+        dir.add_file(file);
+    }
+
+    pub fn cd(&mut self, dir_full_path: String) -> &Directory {
+        crate::trace!("changing directory", path = dir_full_path);
+        self.directories.get(&dir_full_path).unwrap()
+    }
+
+    pub fn size_of(&mut self, dir_full_path: String) -> u32 {
+        if self.directory_sizes.contains_key(&dir_full_path) {
+            return *self.directory_sizes.get(&dir_full_path).unwrap();
+        }
+
+        let mut size = 0;
+        // Temporary binding to avoid borrowing self twice
+        let binding = self.clone();
+        let dir = binding.directories.get(&dir_full_path).unwrap();
+        for file in &dir.files {
+            size += file.size;
+        }
+        for child_dir in &dir.child_directories {
+            let size_of_child = self.size_of(child_dir.clone());
+            size += size_of_child;
+        }
+        self.directory_sizes.insert(dir_full_path.clone(), size);
+        size
+    }
+}
+
+impl Default for FileSystem {
+    fn default() -> FileSystem {
+        FileSystem::new()
+    }
+}
+
+impl Directory {
+    pub fn new_root() -> Directory {
+        Directory {
+            name: String::from("/"),
+            full_path: String::from("/"),
+            files: Vec::new(),
+            child_directories: Vec::new(),
+            parent_directory: None,
+        }
+    }
+
+    pub fn add_file(&mut self, file: File) {
+        self.files.push(file);
+    }
+
+}
+
+impl File {
+    pub fn new(name: String, size: u32) -> File {
+        File {
+            name,
+            size,
+        }
+    }
+}
+
+
+pub fn parse_line(line_number: usize, line: &str) -> Result<LineTypes, ParseError> {
+    let error = |token: &str, expected: &str| ParseError::token(Day7::DAY, line_number, line, token, expected);
+    let tokens: Vec<&str> = line.split(' ').collect();
+    match tokens[..] {
+        ["$", "cd", dir] => Ok(LineTypes::Command(Command::Cd(dir.to_string()))),
+        ["$", "cd"] => Err(ParseError::missing(Day7::DAY, line_number, line, "a directory name")),
+        ["$", "ls"] => Ok(LineTypes::Command(Command::Ls)),
+        ["$", command, ..] => Err(error(command, "a 'cd' or 'ls' command")),
+        ["dir", name] => Ok(LineTypes::Directory(name.to_string())),
+        [size, name] if size.starts_with(|c: char| c.is_ascii_digit()) => {
+            let size = size.parse::<u32>().map_err(|_| error(size, "a file size"))?;
+            Ok(LineTypes::File(size, name.to_string()))
+        },
+        _ => Err(ParseError::line(Day7::DAY, line_number, line, "a '$' command, 'dir <name>' or '<size> <name>'")),
+    }
+}
+
+/// Limits of the puzzle, as sizes in bytes
+#[derive(Clone, Debug)]
+pub struct Day7 {
+    /// Largest directory part 1 adds up
+    pub small_dir_limit: u32,
+    pub disk_size: u32,
+    /// Free space the update needs
+    pub space_needed: u32,
+}
+
+impl Day7 {
+    pub const DEFAULT: Day7 = Day7 { small_dir_limit: 100_000, disk_size: 70_000_000, space_needed: 30_000_000 };
+}
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+    const PARAMS: &'static [Param] = &[
+        Param { name: "small_dir_limit", default: Day7::DEFAULT.small_dir_limit as i64, min: 0, max: u32::MAX as i64 },
+        Param { name: "disk_size", default: Day7::DEFAULT.disk_size as i64, min: 0, max: u32::MAX as i64 },
+        Param { name: "space_needed", default: Day7::DEFAULT.space_needed as i64, min: 0, max: u32::MAX as i64 },
+    ];
+    const EXAMPLES: &'static [Example] = &[
+        Example { input: include_str!("../day 7/test.txt"), part1: Some("95437"), part2: Some("24933642") },
+    ];
+
+    type Parsed = FileSystem;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, content: &str) -> Result<FileSystem, ParseError> {
+        // Create directory structure
+        /*
+        Own directory structure from the root.
+        Traverse through tree by keeping track of location (as a reference) in pwd.
+        Generate new directories and files during parsing
+        */
+        let mut file_system = FileSystem::new();
+        let mut pwd = "/".to_string();
+        let mut is_ls = false;
+        for (i, line) in content.lines().enumerate() {
+            let error = |expected: &str| ParseError::line(Self::DAY, i + 1, line, expected);
+            match parse_line(i + 1, line)? {
+                LineTypes::Command(command) => {
+                    match command {
+                        Command::Cd(dir) => {
+                            if dir == "/" {
+                                pwd = "/".to_string();
+                            } else if dir == ".." {
+                                pwd = file_system.cd(pwd.clone()).parent_directory.clone()
+                                    .ok_or_else(|| error("a directory to leave, already at /"))?;
+                            } else {
+                                let full_path = pwd.clone() + &dir + "/";
+                                if !file_system.directories.contains_key(&full_path) {
+                                    let name = &line[line.len() - dir.len()..];
+                                    return Err(ParseError::token(Self::DAY, i + 1, line, name, "a directory listed by an earlier 'ls'"));
+                                }
+                                pwd = full_path;
+                            }
+                        },
+                        Command::Ls => {
+                            is_ls = true;
+                        },
+                    }
+                },
+                LineTypes::Directory(dir) => {
+                    if !is_ls {
+                        return Err(error("'$ ls' before directory listings"));
+                    }
+                    let new_dir = Directory {
+                        name: dir.clone(),
+                        full_path: pwd.clone() + &dir + "/",
+                        files: Vec::new(),
+                        child_directories: Vec::new(),
+                        parent_directory: Some(pwd.clone()),
+                    };
+                    file_system.mkdir(new_dir.clone());
+                },
+                LineTypes::File(size, file) => {
+                    if !is_ls {
+                        return Err(error("'$ ls' before file listings"));
+                    }
+                    let new_file = File::new(file.clone(), size);
+                    file_system.mkfile(pwd.clone(), new_file);
+                },
+            }
+        }
+        Ok(file_system)
+    }
+
+    fn part1(&self, file_system: &FileSystem) -> u32 {
+        let mut file_system = file_system.clone();
+        // Calculate sizes
+        file_system.size_of("/".to_string());
+        let limit = self.small_dir_limit;
+        let mut size_under_100k = 0;
+        for (dir, size) in file_system.directory_sizes.clone() {
+            if size <= limit {
+                size_under_100k += size;
+            }
+            crate::trace!("directory size", path = dir, size = size);
+        }
+        size_under_100k
+    }
+
+    fn part2(&self, file_system: &FileSystem) -> u32 {
+        let mut file_system = file_system.clone();
+        let full_size = file_system.size_of("/".to_string());
+        let (disk_size, space_needed) = (self.disk_size, self.space_needed);
+        let mut smallest_delete = disk_size;
+        // In u64 since both params go up to u32::MAX
+        let to_free = (full_size as u64 + space_needed as u64).saturating_sub(disk_size as u64);
+        for (_, size) in file_system.directory_sizes.clone() {
+            if size as u64 > to_free && size < smallest_delete {
+                smallest_delete = size;
+            }
+        }
+        smallest_delete
+    }
+
+    fn debug(&self, file_system: &FileSystem) -> Vec<(&'static str, String)> {
+        let mut file_system = file_system.clone();
+        vec![
+            ("directories", file_system.directories.len().to_string()),
+            ("total_size", file_system.size_of("/".to_string()).to_string()),
+        ]
+    }
+
+    fn configure(&mut self, param: &dyn Fn(&str) -> i64) {
+        self.small_dir_limit = param("small_dir_limit") as u32;
+        self.disk_size = param("disk_size") as u32;
+        self.space_needed = param("space_needed") as u32;
+    }
+
+    fn generate(&self, gen: &mut Generator) -> Option<String> {
+        let num_dirs = gen.size(150, 0..=10_000) as usize;
+        let max_files = gen.param("files", 4, 0..=100);
+        let max_file_size = gen.param("file_size", 300_000, 1..=10_000_000);
+        let random_name = |taken: &[String], gen: &mut Generator, extension: bool| loop {
+            let length = gen.rng.range(1..=8);
+            let mut name: String = (0..length).map(|_| (b'a' + gen.rng.range(0..=25) as u8) as char).collect();
+            if extension && gen.rng.range(0..=1) == 1 {
+                name += &format!(".{}", gen.rng.choose(&["txt", "dat", "log", "lst", "ext"]));
+            }
+            if !taken.contains(&name) {
+                return name;
+            }
+        };
+        // Directory 0 is the root, every other directory hangs off a random earlier one
+        let mut dir_names = vec!["/".to_string()];
+        let mut taken: Vec<Vec<String>> = vec![Vec::new()];
+        let mut children: Vec<Vec<usize>> = vec![Vec::new()];
+        let mut files: Vec<Vec<(u64, String)>> = vec![Vec::new()];
+        for id in 1..=num_dirs {
+            let parent = gen.rng.index(id);
+            let name = random_name(&taken[parent], gen, false);
+            taken[parent].push(name.clone());
+            dir_names.push(name);
+            taken.push(Vec::new());
+            children.push(Vec::new());
+            files.push(Vec::new());
+            children[parent].push(id);
+        }
+        let mut total = 0;
+        for id in 0..=num_dirs {
+            for _ in 0..gen.rng.range(0..=max_files) {
+                let name = random_name(&taken[id], gen, true);
+                let size = gen.rng.range(1..=max_file_size);
+                total += size;
+                taken[id].push(name.clone());
+                files[id].push((size, name));
+            }
+        }
+        if total > u32::MAX as u64 {
+            gen.problem(format!("total file size {} does not fit in 32 bits, lower size, files or file_size", total));
+        }
+
+        fn transcript(id: usize, dir_names: &[String], children: &[Vec<usize>], files: &[Vec<(u64, String)>], gen: &mut Generator, out: &mut String) {
+            *out += "$ ls\n";
+            let mut listing: Vec<String> = children[id].iter().map(|&child| format!("dir {}", dir_names[child])).collect();
+            listing.extend(files[id].iter().map(|(size, name)| format!("{} {}", size, name)));
+            gen.rng.shuffle(&mut listing);
+            for line in listing {
+                *out += &line;
+                out.push('\n');
+            }
+            for &child in &children[id] {
+                *out += &format!("$ cd {}\n", dir_names[child]);
+                transcript(child, dir_names, children, files, gen, out);
+                *out += "$ cd ..\n";
+            }
+        }
+        let mut out = String::from("$ cd /\n");
+        transcript(0, &dir_names, &children, &files, gen, &mut out);
+        Some(out)
+    }
+
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        // Every name is numbered in a random order and replaced by its number spelled in letters
+        // (a, b, .., z, aa, ab, ..), so the new names are distinct however many there are
+        fn name_of(line: &str) -> Option<(&str, &str)> {
+            match line.rsplit_once(' ') {
+                Some((prefix, name)) if !line.starts_with("$ ls") && name != "/" && name != ".." => Some((prefix, name)),
+                _ => None,
+            }
+        }
+        let mut names: Vec<&str> = Vec::new();
+        for (_, name) in input.lines().filter_map(name_of) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        rng.shuffle(&mut names);
+        let renamed: HashMap<&str, String> = names
+            .iter()
+            .enumerate()
+            .map(|(i, &name)| {
+                let mut number = i + 1;
+                let mut letters = Vec::new();
+                while number > 0 {
+                    number -= 1;
+                    letters.push(b'a' + (number % 26) as u8);
+                    number /= 26;
+                }
+                (name, letters.iter().rev().map(|&c| c as char).collect())
+            })
+            .collect();
+        let mut out = String::new();
+        for line in input.lines() {
+            let line = match name_of(line) {
+                Some((prefix, name)) => format!("{} {}", prefix, renamed[name]),
+                None => line.to_string(),
+            };
+            out += &line;
+            out.push('\n');
+        }
+        Some(out)
+    }
+}
+
+#[test]
+fn test_params_per_solution() {
+    let file_system = Day7::DEFAULT.parse(Day7::EXAMPLES[0].input).unwrap();
+    assert_eq!(Day7 { space_needed: 0, ..Day7::DEFAULT }.part2(&file_system), 584);
+    // Nothing frees enough, and the sum doesn't overflow
+    assert_eq!(Day7 { space_needed: u32::MAX, ..Day7::DEFAULT }.part2(&file_system), 70_000_000);
+    let configured = crate::find_solution(7).unwrap().with_params(&|name| if name == "small_dir_limit" { 1000 } else { 70_000_000 });
+    let parsed = configured.parse(Day7::EXAMPLES[0].input).unwrap();
+    assert_eq!(configured.part1(parsed.as_ref()), crate::Answer::Number(584));
+    assert_eq!(configured.part2(parsed.as_ref()), crate::Answer::Number(70_000_000));
+}
@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+
+use crate::gen::{Generator, Rng};
+use crate::{Example, ParseError, Solution};
+
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub enum Visibility {
+    Visible,
+    Blocked,
+    Unknown
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West
+}
+
+impl Direction {
+    pub fn step_forward(&self, x: i32, y: i32) -> (i32, i32) {
+        match self {
+            Direction::North => (x, y - 1),
+            Direction::East => (x + 1, y),
+            Direction::South => (x, y + 1),
+            Direction::West => (x - 1, y),
+        }
+    }
+
+    pub fn iter() -> impl Iterator<Item = Direction> {
+        vec![Direction::North, Direction::East, Direction::South, Direction::West].into_iter()
+    }
+}
+
+
+#[derive(Clone, Debug)]
+pub struct Forest {
+    pub trees: Vec<Vec<u8>>,
+    pub height: usize,
+    pub width: usize,
+    pub visibilities: HashMap<Direction, Vec<Vec<Visibility>>>,
+}
+
+/// Forest of trees with visibilities
+/// A tree is visible if it is taller than all trees in any direction or is on the boundary
+/// A tree is blocked if it is shorter than at least one tree in all directions
+impl Forest {
+    pub fn new(trees: Vec<Vec<u8>>) -> Forest {
+        let width = trees[0].len();
+        let height = trees.len();
+        let mut visible_north = vec![vec![Visibility::Unknown; width]; height];
+        let mut visible_east = visible_north.clone();
+        let mut visible_south = visible_north.clone();
+        let mut visible_west = visible_north.clone();
+        let width_idx = width - 1;
+        let height_idx = height - 1;
+
+        // Set boundary visible on the side each direction's sweep starts from
+        visible_north[height_idx].iter_mut().for_each(|v| *v = Visibility::Visible);
+        visible_east.iter_mut().for_each(|v| v[0] = Visibility::Visible);
+        visible_south[0].iter_mut().for_each(|v| *v = Visibility::Visible);
+        visible_west.iter_mut().for_each(|v| v[width_idx] = Visibility::Visible);
+
+        Forest {
+            trees,
+            height,
+            width,
+            visibilities: HashMap::from([
+                (Direction::North, visible_north),
+                (Direction::East, visible_east),
+                (Direction::South, visible_south),
+                (Direction::West, visible_west),
+            ])
+        }
+    }
+
+    pub fn is_visible(&self, x: usize, y: usize) -> bool {
+        for visibility in self.visibilities.values() {
+            if let Visibility::Visible = visibility[y][x] {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Whether some direction's sweep never reached the tree
+    pub fn is_unknown(&self, x: usize, y: usize) -> bool {
+        self.visibilities.values().any(|visibility| visibility[y][x] == Visibility::Unknown)
+    }
+
+    pub fn is_valid_location(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn calculate_visibilities(&mut self) {
+        for direction in Direction::iter() {
+            self._calculate_visibilities_direction(&direction);
+        }
+    }
+
+    fn _calculate_visibilities_direction(&mut self, direction: &Direction) {
+        // Sweep through opposite sides as starting locations for recursion
+        let (x_range, y_range) = match direction {
+            Direction::North => (0..self.width, self.height - 1.. self.height),
+            Direction::East => (0..1, 0..self.height),
+            Direction::South => (0..self.width, 0..1),
+            Direction::West => (self.width - 1..self.width, 0..self.height),
+        };
+        for y in y_range {
+            for x in x_range.clone() {
+                self._visibilities_helper_recurse(direction, x, y, self.trees[y][x]);
+            }
+        }
+    }
+
+    /// Walk away from the edge, carrying the tallest tree seen so far.
+    /// A shorter tree does not end the sweep since a taller one may follow.
+    fn _visibilities_helper_recurse(&mut self, direction: &Direction, x: usize, y: usize, tallest: u8) {
+        let (x, y) = direction.step_forward(x as i32, y as i32);
+        if !self.is_valid_location(x as usize, y as usize) {
+            return;
+        }
+
+        let current_height = self.trees[y as usize][x as usize];
+        if current_height > tallest {
+            self.visibilities.get_mut(direction).unwrap()[y as usize][x as usize] = Visibility::Visible;
+            self._visibilities_helper_recurse(direction, x as usize, y as usize, current_height);
+        } else {
+            self.visibilities.get_mut(direction).unwrap()[y as usize][x as usize] = Visibility::Blocked;
+            self._visibilities_helper_recurse(direction, x as usize, y as usize, tallest);
+        }
+    }
+
+    /// Log a map of each direction's visibilities at trace level
+    pub fn visualize_visibilities(&self) {
+        for direction in Direction::iter() {
+            crate::trace!("visibilities", direction = direction, rows = self._visualize_visibilities_direction(&direction));
+        }
+    }
+
+    fn _visualize_visibilities_direction(&self, direction: &Direction) -> Vec<String> {
+        let direction_rep = match direction {
+            Direction::North => "^",
+            Direction::East => ">",
+            Direction::South => "v",
+            Direction::West => ">",
+        };
+        let mut rows = Vec::new();
+        for y in 0..self.height {
+            let mut row = String::new();
+            for x in 0..self.width {
+                match self.visibilities[direction][y][x] {
+                    Visibility::Visible => row += direction_rep,
+                    Visibility::Blocked => row += "x",
+                    Visibility::Unknown => row += "?",
+                }
+            }
+            rows.push(row);
+        }
+        rows
+    }
+
+    pub fn get_num_visible(&self) -> usize {
+        let mut num_visible = 0;
+        for x in 0..self.width {
+            for y in 0..self.height {
+                if self.is_visible(x, y) {
+                    num_visible += 1;
+                    crate::trace!("visible tree", x = x, y = y);
+                }
+                if self.is_unknown(x, y) {
+                    crate::debug!("tree with unknown visibility", x = x, y = y);
+                }
+            }
+        }
+
+        num_visible
+    }
+
+    /// Product of viewing distances in every direction.
+    /// Viewing distance stops at the edge or at the first tree at least as tall.
+    pub fn scenic_score(&self, x: usize, y: usize) -> usize {
+        let height = self.trees[y][x];
+        let mut score = 1;
+        for direction in Direction::iter() {
+            let mut distance = 0;
+            let (mut cx, mut cy) = direction.step_forward(x as i32, y as i32);
+            while self.is_valid_location(cx as usize, cy as usize) {
+                distance += 1;
+                if self.trees[cy as usize][cx as usize] >= height {
+                    break;
+                }
+                (cx, cy) = direction.step_forward(cx, cy);
+            }
+            score *= distance;
+        }
+        score
+    }
+
+    pub fn get_max_scenic_score(&self) -> usize {
+        let mut max_score = 0;
+        for x in 0..self.width {
+            for y in 0..self.height {
+                max_score = max_score.max(self.scenic_score(x, y));
+            }
+        }
+        max_score
+    }
+}
+
+
+// Basic test from problem
+#[test]
+fn test_basic_part_1() {
+    let content = Day8::EXAMPLES[0].input;
+
+    // Parse content into a vector of vector of chars
+    let trees: Vec<Vec<u8>> = content.lines().map(|line|
+        line.chars().map(|c|
+            c.to_digit(10).unwrap() as u8)
+            .collect::<Vec<u8>>())
+        .collect();
+    let mut forest = Forest::new(trees);
+    // Calculate visibilities for each direction
+    forest.calculate_visibilities();
+
+    // Visualize visibilities
+    forest.visualize_visibilities();
+
+    assert_eq!(forest.get_num_visible(), 21);
+}
+
+
+#[derive(Clone)]
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+    const EXAMPLES: &'static [Example] = &[
+        Example { input: include_str!("../day 8/test.txt"), part1: Some("21"), part2: Some("8") },
+    ];
+
+    type Parsed = Forest;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, content: &str) -> Result<Forest, ParseError> {
+        // Parse content into a vector of vector of digits
+        let mut trees: Vec<Vec<u8>> = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            let mut row = Vec::new();
+            for (offset, c) in line.char_indices() {
+                let height = c.to_digit(10).ok_or_else(|| {
+                    ParseError::token(Self::DAY, i + 1, line, &line[offset..offset + c.len_utf8()], "a tree height 0-9")
+                })?;
+                row.push(height as u8);
+            }
+            if row.is_empty() {
+                return Err(ParseError::line(Self::DAY, i + 1, line, "a row of tree heights"));
+            }
+            if let Some(first) = trees.first() {
+                if row.len() != first.len() {
+                    return Err(ParseError::line(Self::DAY, i + 1, line, format!("a row of {} trees", first.len())));
+                }
+            }
+            trees.push(row);
+        }
+        if trees.is_empty() {
+            return Err(ParseError::missing(Self::DAY, 1, "", "a grid of tree heights"));
+        }
+        Ok(Forest::new(trees))
+    }
+
+    fn part1(&self, forest: &Forest) -> usize {
+        let mut forest = forest.clone();
+
+        forest.calculate_visibilities();
+
+        forest.get_num_visible()
+    }
+
+    fn part2(&self, forest: &Forest) -> usize {
+        forest.get_max_scenic_score()
+    }
+
+    fn debug(&self, forest: &Forest) -> Vec<(&'static str, String)> {
+        vec![
+            ("width", forest.width.to_string()),
+            ("height", forest.height.to_string()),
+        ]
+    }
+
+    fn generate(&self, gen: &mut Generator) -> Option<String> {
+        let width = gen.size(99, 1..=1000);
+        let height = gen.param("height", width, 1..=1000);
+        let tallest = gen.param("max_height", 9, 0..=9);
+        let mut out = String::new();
+        for _ in 0..height {
+            for _ in 0..width {
+                out += &gen.rng.range(0..=tallest).to_string();
+            }
+            out.push('\n');
+        }
+        Some(out)
+    }
+
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        // A random rotation or reflection of the grid keeps both the visible count and the best view
+        let mut grid: Vec<Vec<char>> = input.lines().filter(|line| !line.is_empty()).map(|line| line.chars().collect()).collect();
+        if rng.range(0..=1) == 1 && !grid.is_empty() {
+            grid = (0..grid[0].len()).map(|col| grid.iter().map(|row| row[col]).collect()).collect();
+        }
+        if rng.range(0..=1) == 1 {
+            grid.reverse();
+        }
+        if rng.range(0..=1) == 1 {
+            grid.iter_mut().for_each(|row| row.reverse());
+        }
+        Some(grid.into_iter().map(|row| row.into_iter().chain(['\n']).collect::<String>()).collect())
+    }
+}
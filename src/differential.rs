@@ -0,0 +1,221 @@
+//! Differential testing of the solutions against the Python references in `day N/*.py`.
+//!
+//! A reference reads `input.txt` from its working directory and prints whatever it likes, so it
+//! agrees on a part when that part's answer appears in its output as a separate word.
+
+use std::cell::Cell;
+use std::env;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Once;
+
+use crate::gen::{self, Generator};
+use crate::input::{day_dir, locate};
+use crate::pool::panic_message;
+use crate::{Answer, DynSolution, Part};
+
+/// Environment variable naming the Python interpreter to use
+pub const PYTHON_VAR: &str = "AOC22_PYTHON";
+
+/// Python interpreter from [`PYTHON_VAR`], falling back to `python3` then `python` on the path
+pub fn find_python() -> Option<String> {
+    let candidates = match env::var(PYTHON_VAR) {
+        Ok(python) if !python.is_empty() => vec![python],
+        _ => vec!["python3".to_string(), "python".to_string()],
+    };
+    candidates.into_iter().find(|python| {
+        Command::new(python).arg("--version").output().is_ok_and(|output| output.status.success())
+    })
+}
+
+/// Python references for a day, `day N/*.py` under `root`
+pub fn references(root: &Path, day: u32) -> Vec<PathBuf> {
+    let mut scripts: Vec<PathBuf> = fs::read_dir(root.join(day_dir(day)))
+        .map(|dir| dir.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    scripts.retain(|path| path.extension().is_some_and(|ext| ext == "py"));
+    scripts.sort();
+    scripts
+}
+
+/// Inputs worth comparing on: the examples, the real input when present, and generated ones
+pub fn inputs(solution: &dyn DynSolution, seeds: u64) -> Vec<(String, String)> {
+    let mut inputs: Vec<(String, String)> = solution
+        .examples()
+        .iter()
+        .enumerate()
+        .map(|(i, example)| (format!("example {}", i + 1), example.input.to_string()))
+        .collect();
+    if let Some((path, content)) = locate(solution.day()).ok().and_then(|path| Some((path.clone(), fs::read_to_string(path).ok()?))) {
+        inputs.push((path.display().to_string(), content));
+    }
+    for seed in 0..seeds {
+        if let Ok(input) = gen::generate(solution, Generator::new(seed, None, Default::default())) {
+            inputs.push((format!("generated seed {}", seed), input));
+        }
+    }
+    inputs
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Both answers from the Rust solution, with panics reported as errors instead of printed
+fn solve(solution: &dyn DynSolution, input: &str) -> Result<Vec<(Part, Answer)>, String> {
+    // Minimizing hits plenty of panics, so silence them on this thread only
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+    QUIET.with(|quiet| quiet.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let parsed = solution.parse(input).map_err(|e| e.to_string())?;
+        Ok(Part::both().into_iter().map(|part| (part, solution.solve(parsed.as_ref(), part))).collect())
+    }));
+    QUIET.with(|quiet| quiet.set(false));
+    result.unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(payload))))
+}
+
+/// Standard output of a reference run on `input`, in a scratch directory holding only `input.txt`
+pub fn run_reference(python: &str, script: &Path, input: &str) -> Result<String, String> {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let script = script.canonicalize().map_err(|e| format!("{}: {}", script.display(), e))?;
+    let dir = env::temp_dir().join(format!("aoc22-diff-{}-{}", std::process::id(), RUNS.fetch_add(1, Ordering::Relaxed)));
+    fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let output = fs::write(dir.join("input.txt"), input)
+        .and_then(|_| Command::new(python).arg(&script).current_dir(&dir).output());
+    let _ = fs::remove_dir_all(&dir);
+    let output = output.map_err(|e| format!("could not run {}: {}", python, e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("reference failed: {}", stderr.lines().last().unwrap_or("no output")));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parts whose answer is missing from the reference's output
+pub fn disagreeing(answers: &[(Part, Answer)], output: &str) -> Vec<Part> {
+    let words: Vec<&str> = output.split(|c: char| !c.is_ascii_alphanumeric() && c != '-').collect();
+    answers
+        .iter()
+        .filter(|(_, answer)| !words.contains(&answer.to_string().as_str()))
+        .map(|(part, _)| *part)
+        .collect()
+}
+
+/// Rust answers and reference output for one input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Comparison {
+    pub answers: Vec<(Part, Answer)>,
+    pub output: String,
+    pub disagreeing: Vec<Part>,
+}
+
+/// Run both implementations on `input`, failing when either can't handle it
+pub fn compare(python: &str, solution: &dyn DynSolution, script: &Path, input: &str) -> Result<Comparison, String> {
+    let answers = solve(solution, input)?;
+    let output = run_reference(python, script, input)?;
+    let disagreeing = disagreeing(&answers, &output);
+    Ok(Comparison { answers, output, disagreeing })
+}
+
+/// Remove as many whole lines from `input` as possible while `fails` still holds
+///
+/// Lines keep their own line endings, so a missing final newline survives minimization.
+pub fn minimize(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let mut lines: Vec<&str> = input.split_inclusive('\n').collect();
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        let mut removed = false;
+        let mut start = 0;
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate = lines[..start].concat() + &lines[end..].concat();
+            if fails(&candidate) {
+                lines.drain(start..end);
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+        if !removed {
+            chunk /= 2;
+        }
+    }
+    lines.concat()
+}
+
+/// Smallest input found on which the implementations disagree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Counterexample {
+    /// Name of the input it was minimized from
+    pub source: String,
+    pub input: String,
+    pub comparison: Comparison,
+}
+
+/// Result of comparing one reference against the Rust solution
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScriptReport {
+    pub script: PathBuf,
+    pub agreed: Vec<String>,
+    pub disagreed: Vec<String>,
+    /// Inputs one of the implementations couldn't handle, with the reason
+    pub skipped: Vec<(String, String)>,
+    pub counterexample: Option<Counterexample>,
+}
+
+/// Compare a reference on every input, minimizing the first disagreement
+pub fn check_script(python: &str, solution: &dyn DynSolution, script: &Path, inputs: &[(String, String)]) -> ScriptReport {
+    let mut report = ScriptReport { script: script.to_path_buf(), ..ScriptReport::default() };
+    for (name, input) in inputs {
+        match compare(python, solution, script, input) {
+            Ok(comparison) if comparison.disagreeing.is_empty() => report.agreed.push(name.clone()),
+            Ok(_) => {
+                report.disagreed.push(name.clone());
+                if report.counterexample.is_none() {
+                    let still_fails = |candidate: &str| {
+                        compare(python, solution, script, candidate).is_ok_and(|c| !c.disagreeing.is_empty())
+                    };
+                    let input = minimize(input, still_fails);
+                    let comparison = compare(python, solution, script, &input).expect("minimized input still compares");
+                    report.counterexample = Some(Counterexample { source: name.clone(), input, comparison });
+                }
+            },
+            Err(reason) => report.skipped.push((name.clone(), reason)),
+        }
+    }
+    report
+}
+
+#[test]
+fn test_day1_reference_drops_final_group() {
+    let minimized = minimize("a\nb\nc\nd\n", |candidate| candidate.contains("c\n"));
+    assert_eq!(minimized, "c\n");
+
+    let Some(python) = find_python() else {
+        eprintln!("skipping: no Python interpreter found");
+        return;
+    };
+    let day1 = crate::find_solution(1).unwrap();
+    let script = crate::scaffold::project_root().join(day_dir(1)).join("day_1.py");
+    // A blank line after the last elf makes the reference count it too
+    let comparison = compare(&python, day1, &script, "1\n\n2\n\n3\n\n4\n\n").unwrap();
+    assert!(comparison.disagreeing.is_empty(), "{:?}", comparison);
+
+    let inputs = vec![("example 1".to_string(), day1.examples()[0].input.to_string())];
+    let report = check_script(&python, day1, &script, &inputs);
+    assert_eq!(report.disagreed, vec!["example 1"]);
+    let counterexample = report.counterexample.unwrap();
+    assert!(!counterexample.comparison.disagreeing.is_empty());
+    assert!(counterexample.input.lines().count() < day1.examples()[0].input.lines().count());
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+use std::process;
+
+/// Malformed puzzle input, pointing at the offending part of a line
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column (in characters) where the offending text starts
+    pub column: usize,
+    /// Full text of the offending line
+    pub line_text: String,
+    /// The offending text itself, empty when something is missing
+    pub found: String,
+    /// Description of what was expected instead
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Error covering a whole line
+    pub fn line(day: u32, line: usize, line_text: &str, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            day,
+            line,
+            column: 1,
+            line_text: line_text.to_string(),
+            found: line_text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Error covering `token`, which must be a subslice of `line_text`
+    pub fn token(day: u32, line: usize, line_text: &str, token: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (token.as_ptr() as usize).saturating_sub(line_text.as_ptr() as usize).min(line_text.len());
+        ParseError {
+            day,
+            line,
+            column: line_text[..offset].chars().count() + 1,
+            line_text: line_text.to_string(),
+            found: token.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Error for something missing at the end of a line
+    pub fn missing(day: u32, line: usize, line_text: &str, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            day,
+            line,
+            column: line_text.chars().count() + 1,
+            line_text: line_text.to_string(),
+            found: String::new(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Compiler-style diagnostic showing the offending line of `source`
+    pub fn render(&self, source: &str) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let found = if self.found.is_empty() {
+            "nothing".to_string()
+        } else {
+            format!("'{}'", self.found)
+        };
+        let underline = "^".repeat(self.found.chars().count().max(1));
+        format!(
+            "error: day {}: expected {}, found {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}\n",
+            self.day,
+            self.expected,
+            found,
+            gutter,
+            source,
+            self.line,
+            self.column,
+            gutter,
+            number,
+            self.line_text,
+            gutter,
+            " ".repeat(self.column - 1),
+            underline,
+        )
+    }
+
+    /// Unwrap a parse result for a binary, printing the diagnostic and exiting on failure
+    pub fn or_exit<T>(result: Result<T, ParseError>, source: &str) -> T {
+        result.unwrap_or_else(|e| {
+            eprint!("{}", e.render(source));
+            process::exit(1);
+        })
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day {}, line {}, column {}: expected {}", self.day, self.line, self.column, self.expected)?;
+        if self.found.is_empty() {
+            write!(f, ", found nothing")
+        } else {
+            write!(f, ", found '{}'", self.found)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[test]
+fn test_render_points_at_token() {
+    let line = "64-6x,43-63";
+    let error = ParseError::token(4, 3, line, &line[3..5], "a section number");
+    assert_eq!(error.column, 4);
+    assert_eq!(
+        error.render("day 4/input.txt"),
+        "error: day 4: expected a section number, found '6x'\n --> day 4/input.txt:3:4\n  |\n3 | 64-6x,43-63\n  |    ^^\n"
+    );
+}
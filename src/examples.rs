@@ -0,0 +1,92 @@
+use std::fmt;
+
+use crate::{DynSolution, ParseError, Part};
+
+/// Example whose computed answer differs from the one in the puzzle description
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExampleFailure {
+    Parse { day: u32, example: usize, error: ParseError },
+    Mismatch { day: u32, example: usize, part: Part, expected: String, actual: String },
+}
+
+impl fmt::Display for ExampleFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExampleFailure::Parse { day, example, error } => {
+                write!(f, "day {} example {}: {}", day, example + 1, error)
+            },
+            ExampleFailure::Mismatch { day, example, part, expected, actual } => write!(
+                f,
+                "day {} example {} part {}: expected {}, got {}",
+                day,
+                example + 1,
+                part,
+                expected,
+                actual
+            ),
+        }
+    }
+}
+
+/// Run every declared example of a day, returning the ones that don't give the expected answers
+pub fn check_examples(solution: &dyn DynSolution) -> Vec<ExampleFailure> {
+    let day = solution.day();
+    let mut failures = Vec::new();
+    for (example, case) in solution.examples().iter().enumerate() {
+        let parsed = match solution.parse(case.input) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                failures.push(ExampleFailure::Parse { day, example, error });
+                continue;
+            },
+        };
+        for part in Part::both() {
+            let Some(expected) = case.expected(part) else {
+                continue;
+            };
+            let actual = solution.solve(parsed.as_ref(), part).to_string();
+            if actual != expected {
+                failures.push(ExampleFailure::Mismatch {
+                    day,
+                    example,
+                    part,
+                    expected: expected.to_string(),
+                    actual,
+                });
+            }
+        }
+    }
+    failures
+}
+
+/// Generate one `#[test]` per day that checks all of its examples
+macro_rules! example_tests {
+    ($($name:ident => $solution:expr),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                let failures = check_examples(&$solution);
+                let report: Vec<String> = failures.iter().map(|f| f.to_string()).collect();
+                assert!(failures.is_empty(), "{}", report.join("\n"));
+            }
+        )*
+    };
+}
+
+example_tests! {
+    test_day01_examples => crate::day01::Day1,
+    test_day02_examples => crate::day02::Day2,
+    test_day03_examples => crate::day03::Day3,
+    test_day04_examples => crate::day04::Day4,
+    test_day05_examples => crate::day05::Day5::DEFAULT,
+    test_day06_examples => crate::day06::Day6,
+    test_day07_examples => crate::day07::Day7::DEFAULT,
+    test_day08_examples => crate::day08::Day8,
+}
+
+#[test]
+fn test_every_day_has_examples() {
+    for solution in crate::SOLUTIONS.iter() {
+        assert!(!solution.examples().is_empty(), "day {} declares no examples", solution.day());
+    }
+}
@@ -0,0 +1,147 @@
+//! Random puzzle inputs for `aoc22 gen`, driven by each day's [`Solution::generate`].
+
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use crate::DynSolution;
+
+/// Small deterministic random number generator (SplitMix64), so a seed always gives the same input
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in an inclusive range
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (low, high) = (*range.start(), *range.end());
+        assert!(low <= high, "Empty range {}..={}", low, high);
+        match (high - low).checked_add(1) {
+            Some(span) => low + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    /// Uniform index into a collection of `len` items
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "Cannot pick from nothing");
+        self.range(0..=len as u64 - 1) as usize
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/// Randomness and knobs handed to a day's generator
+///
+/// Generators read their knobs through [`Generator::size`] and [`Generator::param`], which
+/// records them so unknown or out-of-range settings can be reported afterwards.
+pub struct Generator {
+    pub rng: Rng,
+    size: Option<u64>,
+    params: BTreeMap<String, u64>,
+    known: Vec<&'static str>,
+    problems: Vec<String>,
+}
+
+impl Generator {
+    pub fn new(seed: u64, size: Option<u64>, params: BTreeMap<String, u64>) -> Generator {
+        Generator { rng: Rng::new(seed), size, params, known: vec!["size"], problems: Vec::new() }
+    }
+
+    fn check(&mut self, name: &str, value: u64, range: &RangeInclusive<u64>) -> u64 {
+        if range.contains(&value) {
+            value
+        } else {
+            self.problems.push(format!("{} must be from {} to {}, got {}", name, range.start(), range.end(), value));
+            value.clamp(*range.start(), *range.end())
+        }
+    }
+
+    /// The day's main size knob (`--size`), such as a number of lines
+    pub fn size(&mut self, default: u64, range: RangeInclusive<u64>) -> u64 {
+        let value = self.size.unwrap_or(default);
+        self.check("size", value, &range)
+    }
+
+    /// A named knob (`--param name=value`)
+    pub fn param(&mut self, name: &'static str, default: u64, range: RangeInclusive<u64>) -> u64 {
+        self.known.push(name);
+        let value = self.params.get(name).copied().unwrap_or(default);
+        self.check(name, value, &range)
+    }
+
+    /// Report knobs that can't be honoured together
+    pub fn problem(&mut self, message: String) {
+        self.problems.push(message);
+    }
+
+    /// Every problem found while generating, including parameters the day doesn't know
+    fn finish(self) -> Result<(), String> {
+        let mut problems = self.problems;
+        for name in self.params.keys() {
+            if !self.known.contains(&name.as_str()) {
+                problems.push(format!("unknown parameter '{}', expected one of: {}", name, self.known[1..].join(", ")));
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems.join("\n"))
+        }
+    }
+}
+
+/// Generate an input for a day, failing if it has no generator or the knobs are invalid
+pub fn generate(solution: &dyn DynSolution, mut gen: Generator) -> Result<String, String> {
+    let input = solution.generate(&mut gen).ok_or_else(|| format!("day {} has no input generator", solution.day()))?;
+    gen.finish()?;
+    Ok(input)
+}
+
+/// Whether every byte of `window` differs from the others
+pub fn all_distinct(window: &[u8]) -> bool {
+    window.iter().enumerate().all(|(i, c)| !window[..i].contains(c))
+}
+
+#[test]
+fn test_generated_inputs_solve() {
+    // Days fresh from `aoc22 new` have no generator yet
+    let generated = crate::SOLUTIONS.iter().filter(|solution| solution.generate(&mut Generator::new(0, None, BTreeMap::new())).is_some());
+    for solution in generated {
+        for seed in 0..5 {
+            let input = generate(*solution, Generator::new(seed, None, BTreeMap::new())).unwrap();
+            let parsed = solution.parse(&input).unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+            for part in crate::Part::both() {
+                solution.solve(parsed.as_ref(), part);
+            }
+        }
+    }
+
+    let day6 = &crate::day06::Day6;
+    let params = BTreeMap::from([("marker".to_string(), 57), ("message".to_string(), 300)]);
+    let input = generate(day6, Generator::new(3, Some(400), params)).unwrap();
+    assert_eq!(input.len(), 401);
+    let parsed = day6.parse(&input).unwrap();
+    assert_eq!((day6.part1(parsed.as_ref()).to_string(), day6.part2(parsed.as_ref()).to_string()), ("57".to_string(), "300".to_string()));
+
+    let params = BTreeMap::from([("bogus".to_string(), 1)]);
+    assert!(generate(day6, Generator::new(0, None, params)).unwrap_err().contains("unknown parameter 'bogus'"));
+}
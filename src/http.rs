@@ -0,0 +1,250 @@
+//! Minimal HTTP client for talking to the puzzle site.
+//!
+//! Plain `http://` URLs are handled over a [`TcpStream`], which is what the tests' local stub
+//! servers use. `https://` goes through the system `curl`, with headers and body passed on stdin
+//! so the session cookie never shows up in the process list.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    /// Value of the first header named `name`, ignoring case
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug)]
+pub enum HttpError {
+    InvalidUrl(String),
+    Io(io::Error),
+    /// `curl` failed or could not be started
+    Curl(String),
+    Malformed(String),
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpError::InvalidUrl(url) => write!(f, "invalid URL '{}'", url),
+            HttpError::Io(source) => write!(f, "connection failed: {}", source),
+            HttpError::Curl(message) => write!(f, "curl: {}", message),
+            HttpError::Malformed(message) => write!(f, "malformed response: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+impl From<io::Error> for HttpError {
+    fn from(error: io::Error) -> HttpError {
+        HttpError::Io(error)
+    }
+}
+
+/// Parts of an `http://` or `https://` URL
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Url {
+    https: bool,
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl Url {
+    fn parse(url: &str) -> Result<Url, HttpError> {
+        let invalid = || HttpError::InvalidUrl(url.to_string());
+        let (https, rest) = if let Some(rest) = url.strip_prefix("http://") {
+            (false, rest)
+        } else if let Some(rest) = url.strip_prefix("https://") {
+            (true, rest)
+        } else {
+            return Err(invalid());
+        };
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/"),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| invalid())?),
+            None => (authority, if https { 443 } else { 80 }),
+        };
+        if host.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Url { https, host: host.to_string(), port, path: path.to_string() })
+    }
+}
+
+/// Encode pairs as an `application/x-www-form-urlencoded` body
+pub fn form_encode(pairs: &[(&str, &str)]) -> String {
+    let encode = |s: &str| {
+        s.bytes()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+                b' ' => "+".to_string(),
+                b => format!("%{:02X}", b),
+            })
+            .collect::<String>()
+    };
+    pairs.iter().map(|(key, value)| format!("{}={}", encode(key), encode(value))).collect::<Vec<_>>().join("&")
+}
+
+/// Send a request and wait for the whole response
+pub fn request(method: &str, url: &str, headers: &[(&str, &str)], body: Option<&str>) -> Result<Response, HttpError> {
+    let parsed = Url::parse(url)?;
+    if parsed.https {
+        request_curl(method, url, headers, body)
+    } else {
+        request_plain(method, &parsed, headers, body)
+    }
+}
+
+fn request_plain(method: &str, url: &Url, headers: &[(&str, &str)], body: Option<&str>) -> Result<Response, HttpError> {
+    let address = (url.host.as_str(), url.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| HttpError::InvalidUrl(url.host.clone()))?;
+    let mut stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    // HTTP/1.0 so the server closes the connection and never chunks the body
+    let mut head = format!("{} {} HTTP/1.0\r\nHost: {}\r\n", method, url.path, url.host);
+    for (name, value) in headers {
+        head += &format!("{}: {}\r\n", name, value);
+    }
+    let body = body.unwrap_or("");
+    if !body.is_empty() {
+        head += &format!("Content-Length: {}\r\n", body.len());
+    }
+    head += "\r\n";
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body.as_bytes())?;
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw)?;
+    parse_response(&raw)
+}
+
+/// Quote a value for a curl config file
+fn curl_quote(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "\\r");
+    format!("\"{}\"", escaped)
+}
+
+fn request_curl(method: &str, url: &str, headers: &[(&str, &str)], body: Option<&str>) -> Result<Response, HttpError> {
+    let mut config = format!("url = {}\nrequest = {}\n", curl_quote(url), curl_quote(method));
+    for (name, value) in headers {
+        config += &format!("header = {}\n", curl_quote(&format!("{}: {}", name, value)));
+    }
+    if let Some(body) = body {
+        config += &format!("data-binary = {}\n", curl_quote(body));
+    }
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--include", "--http1.1", "--max-time"])
+        .arg(TIMEOUT.as_secs().to_string())
+        .args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| HttpError::Curl(format!("could not run curl: {}", e)))?;
+    child.stdin.take().expect("stdin is piped").write_all(config.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(HttpError::Curl(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    parse_response(&output.stdout)
+}
+
+/// Parse a raw HTTP/1.x response, skipping any interim `1xx` responses
+pub fn parse_response(raw: &[u8]) -> Result<Response, HttpError> {
+    let mut rest = raw;
+    loop {
+        let end = rest
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .ok_or_else(|| HttpError::Malformed("missing end of headers".to_string()))?;
+        let head = String::from_utf8_lossy(&rest[..end]);
+        rest = &rest[end + 4..];
+        let mut lines = head.split("\r\n");
+        let status_line = lines.next().unwrap_or("");
+        let status = status_line
+            .split(' ')
+            .nth(1)
+            .and_then(|code| code.parse::<u16>().ok())
+            .filter(|_| status_line.starts_with("HTTP/"))
+            .ok_or_else(|| HttpError::Malformed(format!("bad status line '{}'", status_line)))?;
+        if (100..200).contains(&status) {
+            continue;
+        }
+        let headers = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect();
+        return Ok(Response { status, headers, body: String::from_utf8_lossy(rest).into_owned() });
+    }
+}
+
+/// Local stand-in server that answers a single request with `response`
+///
+/// Returns the base URL to point a client at and a handle yielding the raw request received.
+#[cfg(test)]
+pub fn serve_once(response: &'static str) -> (String, std::thread::JoinHandle<String>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0; 1024];
+        let complete = |request: &[u8]| {
+            let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") else {
+                return false;
+            };
+            let head = String::from_utf8_lossy(&request[..end]).to_ascii_lowercase();
+            let length = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|n| n.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            request.len() >= end + 4 + length
+        };
+        while !complete(&request) {
+            let n = stream.read(&mut buffer).unwrap();
+            request.extend_from_slice(&buffer[..n]);
+        }
+        stream.write_all(response.as_bytes()).unwrap();
+        String::from_utf8(request).unwrap()
+    });
+    (base_url, server)
+}
+
+#[test]
+fn test_parse_url_and_response() {
+    assert_eq!(
+        Url::parse("http://127.0.0.1:8080/2022/day/1/input").unwrap(),
+        Url { https: false, host: "127.0.0.1".to_string(), port: 8080, path: "/2022/day/1/input".to_string() }
+    );
+    assert_eq!(Url::parse("https://adventofcode.com").unwrap().port, 443);
+    assert!(Url::parse("ftp://example.com").is_err());
+
+    let raw = b"HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 404 Not Found\r\nContent-Type: text/plain\r\n\r\nnope\n";
+    let response = parse_response(raw).unwrap();
+    assert_eq!(response.status, 404);
+    assert_eq!(response.header("content-type"), Some("text/plain"));
+    assert_eq!(response.body, "nope\n");
+    assert_eq!(form_encode(&[("level", "1"), ("answer", "a b&c")]), "level=1&answer=a+b%26c");
+}
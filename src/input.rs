@@ -0,0 +1,164 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable pointing at a directory laid out like the repo (`day N/input.txt`)
+pub const INPUT_DIR_VAR: &str = "AOC22_INPUT_DIR";
+
+/// Where a day's puzzle input comes from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// An explicit file
+    File(PathBuf),
+    /// Standard input
+    Stdin,
+    /// The day's conventional `day N/input.txt`, honouring [`INPUT_DIR_VAR`]
+    Day(u32),
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Read { path: PathBuf, source: io::Error },
+    Stdin(io::Error),
+    /// None of the candidate locations for a day's input exist
+    NotFound { day: u32, tried: Vec<PathBuf> },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Read { path, source } => write!(f, "failed to read {}: {}", path.display(), source),
+            InputError::Stdin(source) => write!(f, "failed to read stdin: {}", source),
+            InputError::NotFound { day, tried } => {
+                write!(f, "no input found for day {}, tried:", day)?;
+                for path in tried {
+                    write!(f, "\n    {}", path.display())?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Read { source, .. } | InputError::Stdin(source) => Some(source),
+            InputError::NotFound { .. } => None,
+        }
+    }
+}
+
+impl InputSource {
+    /// Interpret an `--input` argument: `-` is stdin, anything else is a path
+    pub fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            InputSource::File(path) => read_file(path),
+            InputSource::Stdin => {
+                let mut content = String::new();
+                io::stdin().read_to_string(&mut content).map_err(InputError::Stdin)?;
+                Ok(content)
+            },
+            InputSource::Day(day) => read_file(&locate(*day)?),
+        }
+    }
+
+    /// Buffered reader over the input, for days that can stream it instead of reading it whole
+    pub fn open(&self) -> Result<Box<dyn BufRead>, InputError> {
+        let open_file = |path: &Path| {
+            fs::File::open(path).map_err(|source| InputError::Read { path: path.to_path_buf(), source })
+        };
+        match self {
+            InputSource::File(path) => Ok(Box::new(BufReader::new(open_file(path)?))),
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::Day(day) => Ok(Box::new(BufReader::new(open_file(&locate(*day)?)?))),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Day(day) => write!(f, "{}", day_dir(*day).join("input.txt").display()),
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|source| InputError::Read { path: path.to_path_buf(), source })
+}
+
+/// A data file such as the answers file: the one in the current directory when present, otherwise the crate root's
+pub fn data_file(name: &str) -> PathBuf {
+    let local = PathBuf::from(name);
+    if local.is_file() {
+        local
+    } else {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(name)
+    }
+}
+
+/// Directory holding a day's files, relative to the input root
+pub fn day_dir(day: u32) -> PathBuf {
+    PathBuf::from(format!("day {}", day))
+}
+
+/// Locations searched for a day's input, in order.
+///
+/// When [`INPUT_DIR_VAR`] is set it is the only location, then the configured input
+/// directories are; otherwise the current directory is tried first, then the crate root
+/// so the binaries work from anywhere.
+pub fn candidate_paths(day: u32) -> Vec<PathBuf> {
+    let file = day_dir(day).join("input.txt");
+    if let Some(dir) = std::env::var_os(INPUT_DIR_VAR) {
+        return vec![PathBuf::from(dir).join(file)];
+    }
+    let dirs = &crate::config::get().input_dirs;
+    if !dirs.is_empty() {
+        return dirs.iter().map(|dir| dir.join(&file)).collect();
+    }
+    let mut candidates = vec![file.clone()];
+    let manifest_path = Path::new(env!("CARGO_MANIFEST_DIR")).join(&file);
+    if !candidates.contains(&manifest_path) {
+        candidates.push(manifest_path);
+    }
+    candidates
+}
+
+/// Directory new inputs are saved under: [`INPUT_DIR_VAR`] when set, then the first configured
+/// input directory, otherwise the project root
+pub fn input_root() -> PathBuf {
+    match std::env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => match crate::config::get().input_dirs.first() {
+            Some(dir) => dir.clone(),
+            None => crate::scaffold::project_root(),
+        },
+    }
+}
+
+/// Find the first existing input file for a day
+pub fn locate(day: u32) -> Result<PathBuf, InputError> {
+    let tried = candidate_paths(day);
+    match tried.iter().find(|path| path.is_file()) {
+        Some(path) => Ok(path.clone()),
+        None => Err(InputError::NotFound { day, tried }),
+    }
+}
+
+#[test]
+fn test_source_from_arg() {
+    assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+    assert_eq!(InputSource::from_arg("day 7/input.txt"), InputSource::File(PathBuf::from("day 7/input.txt")));
+}
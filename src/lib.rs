@@ -0,0 +1,57 @@
+pub mod anonymize;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod config;
+pub mod differential;
+pub mod error;
+pub mod examples;
+pub mod gen;
+pub mod http;
+pub mod input;
+pub mod log;
+pub mod output;
+pub mod paragraph;
+pub mod pool;
+pub mod report;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod toml;
+pub mod watch;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+
+pub use error::ParseError;
+pub use solution::{Answer, DynSolution, Example, Param, Part, Solution};
+
+/// Registry of every solved day, in order and with default params
+pub static SOLUTIONS: [&dyn DynSolution; 8] = [
+    &day01::Day1,
+    &day02::Day2,
+    &day03::Day3,
+    &day04::Day4,
+    &day05::Day5::DEFAULT,
+    &day06::Day6,
+    &day07::Day7::DEFAULT,
+    &day08::Day8,
+];
+
+/// Look up a day's solution in the registry
+pub fn find_solution(day: u32) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS.iter().find(|s| s.day() == day).copied()
+}
+
+#[test]
+fn test_registry_is_ordered_and_unique() {
+    let days: Vec<u32> = SOLUTIONS.iter().map(|s| s.day()).collect();
+    assert!(days.windows(2).all(|pair| pair[0] < pair[1]), "days out of order: {:?}", days);
+    assert!(days.iter().all(|day| (1..=25).contains(day)));
+}
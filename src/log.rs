@@ -0,0 +1,181 @@
+//! Leveled diagnostics on stderr, filtered per target.
+//!
+//! An event's target is the module that logs it without the crate name, so each day has its own
+//! (`day06`). The filter comes from `-v`/`-vv` and [`LOG_VAR`], a comma separated list of
+//! `level` or `target=level` directives such as `warn,day07=trace`.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Environment variable holding the log filter
+pub const LOG_VAR: &str = "AOC22_LOG";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /// Most detailed level shown for a number of `-v` flags
+    pub fn from_verbosity(verbosity: u8) -> Level {
+        match verbosity {
+            0 => Level::Warn,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        f.pad(name)
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Level, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("Invalid log level '{}', expected error, warn, info, debug or trace", s)),
+        }
+    }
+}
+
+/// Most detailed level shown, overall and for particular targets
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Filter {
+    pub default: Level,
+    pub targets: Vec<(String, Level)>,
+}
+
+impl Filter {
+    /// Filter from directives like `warn,day07=trace`, starting from `default`
+    pub fn parse(spec: &str, default: Level) -> Result<Filter, String> {
+        let mut filter = Filter { default, targets: Vec::new() };
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => filter.targets.push((target.trim().to_string(), level.trim().parse()?)),
+                None => filter.default = directive.parse()?,
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Level for a target, from the most specific matching directive
+    pub fn level(&self, target: &str) -> Level {
+        self.targets
+            .iter()
+            .filter(|(prefix, _)| {
+                target == prefix || target.strip_prefix(prefix.as_str()).is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+}
+
+static FILTER: OnceLock<Filter> = OnceLock::new();
+
+/// Set up the filter from `-v` flags and [`LOG_VAR`], which only takes effect before the first event
+///
+/// A bad [`LOG_VAR`] is reported and otherwise ignored. Without a call, events use [`LOG_VAR`] alone.
+pub fn init(verbosity: u8) {
+    let _ = FILTER.set(filter_from_env(Level::from_verbosity(verbosity)));
+}
+
+fn filter_from_env(default: Level) -> Filter {
+    let spec = std::env::var(LOG_VAR).unwrap_or_default();
+    Filter::parse(&spec, default).unwrap_or_else(|e| {
+        eprintln!("warning: ignoring {}: {}", LOG_VAR, e);
+        Filter { default, targets: Vec::new() }
+    })
+}
+
+/// Target of events logged from a module, e.g. `day06` for `aoc22::day06`
+pub fn target(module_path: &str) -> &str {
+    module_path.split_once("::").map_or(module_path, |(_, rest)| rest)
+}
+
+pub fn enabled(module_path: &str, level: Level) -> bool {
+    level <= FILTER.get_or_init(|| filter_from_env(Level::Warn)).level(target(module_path))
+}
+
+/// Render an event as a single line, fields as `key=value` with debug formatting
+pub fn format_event(module_path: &str, level: Level, message: &str, fields: &[(&str, String)]) -> String {
+    let mut line = format!("{:<5} {}: {}", level, target(module_path), message);
+    for (key, value) in fields {
+        line += &format!(" {}={}", key, value);
+    }
+    line
+}
+
+pub fn emit(module_path: &str, level: Level, message: &str, fields: &[(&str, String)]) {
+    eprintln!("{}", format_event(module_path, level, message, fields));
+}
+
+/// Log an event with optional `key = value` fields, e.g. `log!(Level::Debug, "marker found", index = i)`
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $message:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::log::enabled(module_path!(), $level) {
+            $crate::log::emit(module_path!(), $level, &$message, &[$((stringify!($key), format!("{:?}", $value))),*]);
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($args:tt)*) => { $crate::log!($crate::log::Level::Error, $($args)*) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($args:tt)*) => { $crate::log!($crate::log::Level::Warn, $($args)*) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($args:tt)*) => { $crate::log!($crate::log::Level::Info, $($args)*) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($args:tt)*) => { $crate::log!($crate::log::Level::Debug, $($args)*) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($args:tt)*) => { $crate::log!($crate::log::Level::Trace, $($args)*) };
+}
+
+#[test]
+fn test_filter_directives() {
+    let filter = Filter::parse("info, day07=trace,day07::fs=error", Level::Warn).unwrap();
+    assert_eq!(filter.level("day06"), Level::Info);
+    assert_eq!(filter.level("day07"), Level::Trace);
+    assert_eq!(filter.level("day07::fs"), Level::Error);
+    assert_eq!(filter.level("day070"), Level::Info);
+    assert!(Filter::parse("day07=loud", Level::Warn).is_err());
+    assert_eq!(target("aoc22::day06"), "day06");
+    assert_eq!(
+        format_event("aoc22::day06", Level::Debug, "marker found", &[("index", "7".to_string()), ("window", "\"jpqm\"".to_string())]),
+        "DEBUG day06: marker found index=7 window=\"jpqm\""
+    );
+}
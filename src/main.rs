@@ -1,3 +1,992 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{env, thread};
+
+use aoc22::anonymize;
+use aoc22::answers::{self, AnswerDb, AnswerRecord, AnswersError, Verdict};
+use aoc22::bench;
+use aoc22::client::{Client, ClientError, Fetched};
+use aoc22::config::{self, Config};
+use aoc22::differential::{self, PYTHON_VAR};
+use aoc22::gen::{self, Generator};
+use aoc22::input::{InputError, InputSource};
+use aoc22::log;
+use aoc22::output::{Format, PartResult};
+use aoc22::pool;
+use aoc22::report;
+use aoc22::scaffold::{self, ScaffoldError};
+use aoc22::submit::{self, History, Outcome, SubmitError};
+use aoc22::watch::{self, Snapshot};
+use aoc22::{find_solution, DynSolution, ParseError, Part, SOLUTIONS};
+
+const USAGE: &str = "\
+Usage: aoc22 [-v|-vv] <command>
+
+Commands:
+    run <day|all> [options]    Run a day's solution (both parts by default)
+    bench <day|all> [options]  Time parse, part 1 and part 2 separately
+    verify [day|all] [options] Check answers against the recorded answers file
+    new <day> [--title <text>] Generate and register a skeleton for a new day
+    fetch <day>                Download a day's input unless it is already present
+    submit <day> <part>        Send a computed answer to the site
+    watch <day> [options]      Rerun a day and its examples whenever its files change
+    gen <day> [options]        Print a random valid input for a day
+    anonymize <day> [options]  Print a shareable equivalent of a day's input
+    diff [day|all] [options]   Compare answers with the Python references in 'day N/*.py'
+    report [options]           Write a Markdown page of every day's answers, parts verified against
+                               answers.toml, runtime and solver module lines of code
+    list                       List available days
+    help                       Show this message
+
+Run options:
+    -p, --part <1|2|both>      Only run one part, or both even if the config picks one
+    -i, --input <path|->       Read input from a file, or stdin for '-' (single day only)
+    -f, --format <format>      text, csv or json lines (default text)
+    --debug                    Include details about the parsed input
+    -j, --jobs <count>         Run all days on this many threads and print a summary
+
+Bench options:
+    -n, --iterations <count>   Timed runs per phase (default 20)
+    -f, --format <format>      text, csv or json (default text)
+
+Verify options:
+    --answers <path>           Answers file (default answers.toml)
+    --record                   Record answers for inputs that have none yet
+
+Submit options:
+    --history <path>           Submission history (default submissions.toml)
+
+Watch options:
+    --interval <ms>            How often to check for changes (default 500)
+
+Gen options:
+    --seed <number>            Seed for a reproducible input (default random, printed to stderr)
+    --size <number>            Main size knob, such as the number of lines
+    --param <name>=<number>    Day-specific knob, may be repeated
+
+Anonymize options:
+    -i, --input <path|->       Input to anonymize (default the day's input)
+    --seed <number>            Seed for a reproducible result (default random, printed to stderr)
+
+Diff options:
+    --seeds <count>            Generated inputs to compare on besides the examples and input (default 3)
+
+Report options:
+    -o, --output <path>        Write the Markdown to a file instead of stdout
+    --html <path>              Also write a standalone HTML page
+    --redact                   Leave answers out
+
+Inputs default to 'day N/input.txt' under $AOC22_INPUT_DIR when set,
+otherwise under the current directory or the crate root.
+
+fetch and submit read the session cookie from $AOC22_SESSION or ~/.config/aoc22/session
+and talks to $AOC22_BASE_URL when set instead of adventofcode.com.
+
+Defaults for inputs, formats, parts, bench iterations and day parameters can be set in
+aoc22.toml at the project root, and overridden in ~/.config/aoc22/config.toml.
+
+-v and -vv show debug and trace diagnostics on stderr. $AOC22_LOG sets levels per
+target instead, e.g. 'warn,day07=trace'.";
+
+const DEFAULT_BENCH_ITERATIONS: usize = 20;
+const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_millis(500);
+const DEFAULT_DIFF_SEEDS: u64 = 3;
+
+/// Which days to run
+#[derive(Debug, PartialEq)]
+enum Selection {
+    All,
+    Day(u32),
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct RunOptions {
+    part: Option<Part>,
+    input: Option<InputSource>,
+    format: Format,
+    debug: bool,
+    /// Worker threads for `run all`, sequential when unset
+    jobs: Option<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Cli {
+    Run { selection: Selection, options: RunOptions },
+    Bench { selection: Selection, iterations: usize, format: Format },
+    Verify { selection: Selection, answers: Option<PathBuf>, record: bool },
+    New { day: u32, title: String },
+    Fetch { day: u32 },
+    Submit { day: u32, part: Part, history: Option<PathBuf> },
+    Watch { day: u32, interval: Duration },
+    Gen { day: u32, seed: Option<u64>, size: Option<u64>, params: BTreeMap<String, u64> },
+    Anonymize { day: u32, input: Option<InputSource>, seed: Option<u64> },
+    Diff { selection: Selection, seeds: u64 },
+    Report { output: Option<PathBuf>, html: Option<PathBuf>, redact: bool },
+    List,
+    Help,
+}
+
+/// Parse the command line, taking defaults from `config` where it sets them
+fn parse_args(args: &[String], config: &Config) -> Result<Cli, String> {
+    let mut args = args.iter();
+    let command = match args.next() {
+        Some(command) => command,
+        None => return Ok(Cli::Help),
+    };
+    match command.as_str() {
+        "run" => {
+            let mut selection = None;
+            let mut options = RunOptions { part: config.part, format: config.format.unwrap_or_default(), ..RunOptions::default() };
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--part" | "-p" => {
+                        let value = args.next().ok_or("--part requires a value")?;
+                        options.part = if value == "both" { None } else { Some(parse_part(value)?) };
+                    },
+                    "--input" | "-i" => {
+                        let value = args.next().ok_or("--input requires a path or '-'")?;
+                        options.input = Some(InputSource::from_arg(value));
+                    },
+                    "--format" | "-f" => {
+                        let value = args.next().ok_or("--format requires a value")?;
+                        options.format = value.parse()?;
+                    },
+                    "--debug" => options.debug = true,
+                    "--jobs" | "-j" => {
+                        let value = args.next().ok_or("--jobs requires a count")?;
+                        let jobs = value.parse::<usize>()
+                            .ok()
+                            .filter(|&n| n > 0)
+                            .ok_or_else(|| format!("Invalid job count '{}'", value))?;
+                        options.jobs = Some(jobs);
+                    },
+                    _ if selection.is_none() => selection = Some(parse_selection(arg)?),
+                    _ => return Err(format!("Unexpected argument '{}'", arg)),
+                }
+            }
+            let selection = selection.ok_or("run requires a day or 'all'")?;
+            if selection == Selection::All && options.input.is_some() {
+                return Err("--input can only be used with a single day".to_string());
+            }
+            if selection != Selection::All && options.jobs.is_some() {
+                return Err("--jobs can only be used with 'all'".to_string());
+            }
+            Ok(Cli::Run { selection, options })
+        },
+        "bench" => {
+            let mut selection = None;
+            let mut iterations = config.bench_iterations.unwrap_or(DEFAULT_BENCH_ITERATIONS);
+            let mut format = config.format.unwrap_or_default();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--iterations" | "-n" => {
+                        let value = args.next().ok_or("--iterations requires a count")?;
+                        iterations = value.parse::<usize>()
+                            .ok()
+                            .filter(|&n| n > 0)
+                            .ok_or_else(|| format!("Invalid iteration count '{}'", value))?;
+                    },
+                    "--format" | "-f" => {
+                        let value = args.next().ok_or("--format requires a value")?;
+                        format = value.parse()?;
+                    },
+                    _ if selection.is_none() => selection = Some(parse_selection(arg)?),
+                    _ => return Err(format!("Unexpected argument '{}'", arg)),
+                }
+            }
+            let selection = selection.ok_or("bench requires a day or 'all'")?;
+            Ok(Cli::Bench { selection, iterations, format })
+        },
+        "verify" => {
+            let mut selection = None;
+            let mut answers = None;
+            let mut record = false;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--answers" => {
+                        let value = args.next().ok_or("--answers requires a path")?;
+                        answers = Some(PathBuf::from(value));
+                    },
+                    "--record" => record = true,
+                    _ if selection.is_none() => selection = Some(parse_selection(arg)?),
+                    _ => return Err(format!("Unexpected argument '{}'", arg)),
+                }
+            }
+            let selection = selection.unwrap_or(Selection::All);
+            Ok(Cli::Verify { selection, answers, record })
+        },
+        "new" => {
+            let mut day = None;
+            let mut title = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--title" => {
+                        let value = args.next().ok_or("--title requires a value")?;
+                        title = Some(value.clone());
+                    },
+                    _ if day.is_none() => day = Some(parse_puzzle_day(arg)?),
+                    _ => return Err(format!("Unexpected argument '{}'", arg)),
+                }
+            }
+            let day = day.ok_or("new requires a day")?;
+            let title = title.unwrap_or_else(|| format!("Day {}", day));
+            Ok(Cli::New { day, title })
+        },
+        "fetch" => match (args.next(), args.next()) {
+            (Some(day), None) => Ok(Cli::Fetch { day: parse_puzzle_day(day)? }),
+            (None, _) => Err("fetch requires a day".to_string()),
+            (Some(_), Some(extra)) => Err(format!("Unexpected argument '{}'", extra)),
+        },
+        "submit" => {
+            let mut positional = Vec::new();
+            let mut history = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--history" => {
+                        let value = args.next().ok_or("--history requires a path")?;
+                        history = Some(PathBuf::from(value));
+                    },
+                    _ => positional.push(arg),
+                }
+            }
+            match positional[..] {
+                [day, part] => Ok(Cli::Submit { day: parse_day(day)?, part: parse_part(part)?, history }),
+                [_, _, extra, ..] => Err(format!("Unexpected argument '{}'", extra)),
+                _ => Err("submit requires a day and a part".to_string()),
+            }
+        },
+        "watch" => {
+            let mut day = None;
+            let mut interval = DEFAULT_WATCH_INTERVAL;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--interval" => {
+                        let value = args.next().ok_or("--interval requires a number of milliseconds")?;
+                        interval = value.parse::<u64>()
+                            .ok()
+                            .filter(|&ms| ms > 0)
+                            .map(Duration::from_millis)
+                            .ok_or_else(|| format!("Invalid interval '{}'", value))?;
+                    },
+                    _ if day.is_none() => day = Some(parse_day(arg)?),
+                    _ => return Err(format!("Unexpected argument '{}'", arg)),
+                }
+            }
+            let day = day.ok_or("watch requires a day")?;
+            Ok(Cli::Watch { day, interval })
+        },
+        "gen" => {
+            let mut day = None;
+            let mut seed = None;
+            let mut size = None;
+            let mut params = BTreeMap::new();
+            let number = |flag: &str, value: &str| value.parse::<u64>().map_err(|_| format!("Invalid {} '{}'", flag, value));
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--seed" => seed = Some(number("seed", args.next().ok_or("--seed requires a number")?)?),
+                    "--size" => size = Some(number("size", args.next().ok_or("--size requires a number")?)?),
+                    "--param" => {
+                        let value = args.next().ok_or("--param requires name=value")?;
+                        let (name, value) = value.split_once('=').ok_or_else(|| format!("Invalid parameter '{}', expected name=value", value))?;
+                        if params.insert(name.to_string(), number(name, value)?).is_some() {
+                            return Err(format!("Parameter '{}' given twice", name));
+                        }
+                    },
+                    _ if day.is_none() => day = Some(parse_day(arg)?),
+                    _ => return Err(format!("Unexpected argument '{}'", arg)),
+                }
+            }
+            let day = day.ok_or("gen requires a day")?;
+            Ok(Cli::Gen { day, seed, size, params })
+        },
+        "anonymize" => {
+            let mut day = None;
+            let mut input = None;
+            let mut seed = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--input" | "-i" => {
+                        let value = args.next().ok_or("--input requires a path or '-'")?;
+                        input = Some(InputSource::from_arg(value));
+                    },
+                    "--seed" => {
+                        let value = args.next().ok_or("--seed requires a number")?;
+                        seed = Some(value.parse::<u64>().map_err(|_| format!("Invalid seed '{}'", value))?);
+                    },
+                    _ if day.is_none() => day = Some(parse_day(arg)?),
+                    _ => return Err(format!("Unexpected argument '{}'", arg)),
+                }
+            }
+            let day = day.ok_or("anonymize requires a day")?;
+            Ok(Cli::Anonymize { day, input, seed })
+        },
+        "diff" => {
+            let mut selection = None;
+            let mut seeds = DEFAULT_DIFF_SEEDS;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--seeds" => {
+                        let value = args.next().ok_or("--seeds requires a count")?;
+                        seeds = value.parse::<u64>().map_err(|_| format!("Invalid seed count '{}'", value))?;
+                    },
+                    _ if selection.is_none() => selection = Some(parse_selection(arg)?),
+                    _ => return Err(format!("Unexpected argument '{}'", arg)),
+                }
+            }
+            let selection = selection.unwrap_or(Selection::All);
+            Ok(Cli::Diff { selection, seeds })
+        },
+        "report" => {
+            let mut output = None;
+            let mut html = None;
+            let mut redact = false;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--output" | "-o" => {
+                        let value = args.next().ok_or("--output requires a path")?;
+                        output = Some(PathBuf::from(value));
+                    },
+                    "--html" => {
+                        let value = args.next().ok_or("--html requires a path")?;
+                        html = Some(PathBuf::from(value));
+                    },
+                    "--redact" => redact = true,
+                    _ => return Err(format!("Unexpected argument '{}'", arg)),
+                }
+            }
+            Ok(Cli::Report { output, html, redact })
+        },
+        "list" => Ok(Cli::List),
+        "help" | "--help" | "-h" => Ok(Cli::Help),
+        _ => Err(format!("Unknown command '{}'", command)),
+    }
+}
+
+/// Remove `-v`, `-vv` and `--verbose` from anywhere in the arguments, returning the verbosity
+fn take_verbosity(args: &mut Vec<String>) -> u8 {
+    let mut verbosity: u8 = 0;
+    args.retain(|arg| {
+        let count = match arg.as_str() {
+            "-v" | "--verbose" => 1,
+            "-vv" => 2,
+            _ => return true,
+        };
+        verbosity = verbosity.saturating_add(count);
+        false
+    });
+    verbosity
+}
+
+fn parse_selection(value: &str) -> Result<Selection, String> {
+    match value {
+        "all" => Ok(Selection::All),
+        _ => Ok(Selection::Day(parse_day(value)?)),
+    }
+}
+
+fn parse_day(value: &str) -> Result<u32, String> {
+    let day = value.parse::<u32>().map_err(|_| format!("Invalid day '{}'", value))?;
+    if find_solution(day).is_none() {
+        return Err(format!("Day {} is not solved yet", day));
+    }
+    Ok(day)
+}
+
+/// Any puzzle day, solved or not
+fn parse_puzzle_day(value: &str) -> Result<u32, String> {
+    value.parse::<u32>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| format!("Invalid day '{}', expected 1 to 25", value))
+}
+
+fn parse_part(value: &str) -> Result<Part, String> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("Invalid part '{}', expected 1 or 2", value)),
+    }
+}
+
+/// Failure of a command, reported by `main`
+#[derive(Debug)]
+enum CliError {
+    Message(String),
+    /// Malformed input, shown as a diagnostic against the input it came from
+    Parse { error: ParseError, source: String },
+}
+
+impl From<String> for CliError {
+    fn from(message: String) -> CliError {
+        CliError::Message(message)
+    }
+}
+
+impl From<InputError> for CliError {
+    fn from(error: InputError) -> CliError {
+        CliError::Message(error.to_string())
+    }
+}
+
+impl From<AnswersError> for CliError {
+    fn from(error: AnswersError) -> CliError {
+        CliError::Message(error.to_string())
+    }
+}
+
+impl From<ScaffoldError> for CliError {
+    fn from(error: ScaffoldError) -> CliError {
+        CliError::Message(error.to_string())
+    }
+}
+
+impl From<ClientError> for CliError {
+    fn from(error: ClientError) -> CliError {
+        CliError::Message(error.to_string())
+    }
+}
+
+impl From<SubmitError> for CliError {
+    fn from(error: SubmitError) -> CliError {
+        CliError::Message(error.to_string())
+    }
+}
+
+/// A day's solution with its params taken from the configuration
+fn configured(solution: &dyn DynSolution) -> Box<dyn DynSolution> {
+    let config = config::get();
+    solution.with_params(&|name| config.param(solution.day(), name))
+}
+
+/// Read and parse a day's input, returning the raw content alongside the parsed form
+fn load(solution: &dyn DynSolution, input: &InputSource) -> Result<(String, Box<dyn Any>), CliError> {
+    let content = input.read()?;
+    let parsed = solution.parse(&content)
+        .map_err(|error| CliError::Parse { error, source: input.to_string() })?;
+    Ok((content, parsed))
+}
+
+/// Everything `run` prints for one day
+struct DayRun {
+    debug: Vec<(&'static str, String)>,
+    results: Vec<PartResult>,
+}
+
+fn solve_day(solution: &dyn DynSolution, input: &InputSource, options: &RunOptions) -> Result<DayRun, CliError> {
+    let (_, parsed) = load(solution, input)?;
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => Part::both().to_vec(),
+    };
+    let debug = if options.debug {
+        solution.debug(parsed.as_ref())
+    } else {
+        Vec::new()
+    };
+    let results = parts
+        .into_iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = solution.solve(parsed.as_ref(), part);
+            PartResult { day: solution.day(), part, answer, duration: start.elapsed(), debug: debug.clone() }
+        })
+        .collect();
+    Ok(DayRun { debug, results })
+}
+
+fn print_day(solution: &dyn DynSolution, day_run: &DayRun, format: Format) {
+    if format == Format::Text {
+        println!("Day {}: {}", solution.day(), solution.title());
+        for (key, value) in &day_run.debug {
+            println!("  {}: {}", key, value);
+        }
+    }
+    for result in &day_run.results {
+        match format {
+            Format::Text => println!("Part {}: {}", result.part, result.answer),
+            Format::Csv => println!("{}", result.to_csv()),
+            Format::Json => println!("{}", result.to_json()),
+        }
+    }
+}
+
+fn run_day(solution: &dyn DynSolution, input: &InputSource, options: &RunOptions) -> Result<(), CliError> {
+    let day_run = solve_day(solution, input, options)?;
+    print_day(solution, &day_run, options.format);
+    Ok(())
+}
+
+/// Run every day on `jobs` threads, then print them in order followed by a summary table
+fn run_parallel(jobs: usize, options: &RunOptions) -> Result<(), CliError> {
+    let outcomes = pool::run_pool(jobs, SOLUTIONS.to_vec(), |solution| {
+        let start = Instant::now();
+        let day_run = solve_day(configured(solution).as_ref(), &InputSource::Day(solution.day()), options).map_err(|error| match error {
+            CliError::Message(message) => message,
+            CliError::Parse { error, .. } => error.to_string(),
+        });
+        (day_run, start.elapsed())
+    });
+
+    let mut summary = format!("{:>3}  {:<24} {:>14} {:>14} {:>10}  {}\n", "day", "title", "part 1", "part 2", "time", "status");
+    let mut failures = 0;
+    for (solution, outcome) in SOLUTIONS.iter().zip(outcomes) {
+        let (day_run, elapsed) = match outcome {
+            Ok((day_run, elapsed)) => (day_run, Some(elapsed)),
+            Err(panic) => (Err(format!("panicked: {}", panic)), None),
+        };
+        let answer = |part: Part| match &day_run {
+            Ok(day_run) => day_run.results.iter().find(|r| r.part == part).map_or("-".to_string(), |r| r.answer.to_string()),
+            Err(_) => "-".to_string(),
+        };
+        let status = match &day_run {
+            Ok(day_run) => {
+                print_day(*solution, day_run, options.format);
+                "ok".to_string()
+            },
+            Err(message) => {
+                failures += 1;
+                eprintln!("error: day {}: {}", solution.day(), message);
+                format!("FAILED: {}", message.split_whitespace().collect::<Vec<_>>().join(" "))
+            },
+        };
+        summary += &format!(
+            "{:>3}  {:<24} {:>14} {:>14} {:>10}  {}\n",
+            solution.day(),
+            solution.title(),
+            answer(Part::One),
+            answer(Part::Two),
+            elapsed.map_or("-".to_string(), |e| format!("{:.2?}", e)),
+            status,
+        );
+    }
+    // Machine-readable formats stay parseable, failures are already on stderr
+    if options.format == Format::Text {
+        print!("\n{}", summary);
+    }
+    if failures > 0 {
+        return Err(format!("{} of {} days failed", failures, SOLUTIONS.len()).into());
+    }
+    Ok(())
+}
+
+fn run(selection: Selection, options: RunOptions) -> Result<(), CliError> {
+    if options.format == Format::Csv {
+        println!("{}", PartResult::CSV_HEADER);
+    }
+    match selection {
+        Selection::All => {
+            if let Some(jobs) = options.jobs {
+                return run_parallel(jobs, &options);
+            }
+            for solution in SOLUTIONS.iter() {
+                run_day(configured(*solution).as_ref(), &InputSource::Day(solution.day()), &options)?;
+            }
+            Ok(())
+        },
+        Selection::Day(day) => {
+            let input = options.input.clone().unwrap_or(InputSource::Day(day));
+            run_day(configured(find_solution(day).unwrap()).as_ref(), &input, &options)
+        },
+    }
+}
+
+impl Selection {
+    /// Solutions of the selected days, configured
+    fn solutions(&self) -> Vec<Box<dyn DynSolution>> {
+        let solutions = match self {
+            Selection::All => SOLUTIONS.to_vec(),
+            Selection::Day(day) => vec![find_solution(*day).unwrap()],
+        };
+        solutions.into_iter().map(configured).collect()
+    }
+}
+
+fn bench(selection: Selection, iterations: usize, format: Format) -> Result<(), CliError> {
+    let mut results = Vec::new();
+    for solution in selection.solutions().iter().map(Box::as_ref) {
+        let input = InputSource::Day(solution.day());
+        let (content, _) = load(solution, &input)?;
+        let result = bench::bench_day(solution, &content, iterations)
+            .map_err(|error| CliError::Parse { error, source: input.to_string() })?;
+        results.push(result);
+    }
+    print!("{}", bench::render(&results, format));
+    Ok(())
+}
+
+fn verify(selection: Selection, answers: Option<PathBuf>, record: bool) -> Result<(), CliError> {
+    let path = answers.unwrap_or_else(answers::default_path);
+    let mut db = AnswerDb::load(&path)?;
+    let mut mismatches = 0;
+    let mut recorded = 0;
+    for solution in selection.solutions().iter().map(Box::as_ref) {
+        let (content, parsed) = load(solution, &InputSource::Day(solution.day()))?;
+        let hash = answers::input_hash(&content);
+        for part in Part::both() {
+            let actual = solution.solve(parsed.as_ref(), part).to_string();
+            let status = match db.check(solution.day(), part, &hash, &actual) {
+                Verdict::Match => "ok".to_string(),
+                Verdict::Mismatch { expected } => {
+                    mismatches += 1;
+                    format!("MISMATCH (expected {})", expected)
+                },
+                Verdict::Unknown if record => {
+                    db.insert(AnswerRecord { day: solution.day(), part, input_hash: hash.clone(), answer: actual.clone() });
+                    recorded += 1;
+                    "recorded".to_string()
+                },
+                Verdict::Unknown => "unrecorded".to_string(),
+            };
+            println!("day {} part {}: {} {}", solution.day(), part, actual, status);
+        }
+    }
+    if recorded > 0 {
+        db.save(&path)?;
+        println!("Recorded {} new answer(s) in {}", recorded, path.display());
+    }
+    if mismatches > 0 {
+        return Err(format!("{} answer(s) differ from {}", mismatches, path.display()).into());
+    }
+    Ok(())
+}
+
+fn new_day(day: u32, title: &str) -> Result<(), CliError> {
+    let root = scaffold::project_root();
+    for path in scaffold::scaffold(&root, day, title)? {
+        let path = path.strip_prefix(&root).unwrap_or(&path);
+        println!("wrote {}", path.display());
+    }
+    let example = aoc22::input::day_dir(day).join(scaffold::EXAMPLE_FILE);
+    println!("Paste the example from the puzzle into '{}' and fill in its expected answers", example.display());
+    Ok(())
+}
+
+fn fetch(day: u32) -> Result<(), CliError> {
+    // Anywhere the runner would already find an input counts as cached
+    if let Ok(path) = aoc22::input::locate(day) {
+        println!("day {}: already have {}", day, path.display());
+        return Ok(());
+    }
+    let client = Client::from_env()?;
+    match client.fetch_input(day, &aoc22::input::input_root())? {
+        Fetched::Cached(path) => println!("day {}: already have {}", day, path.display()),
+        Fetched::Downloaded(path) => println!("day {}: saved {}", day, path.display()),
+    }
+    Ok(())
+}
+
+fn submit(day: u32, part: Part, history: Option<PathBuf>) -> Result<(), CliError> {
+    let solution = configured(find_solution(day).unwrap());
+    let (_, parsed) = load(solution.as_ref(), &InputSource::Day(day))?;
+    let answer = solution.solve(parsed.as_ref(), part).to_string();
+    let path = history.unwrap_or_else(submit::default_path);
+    let mut history = History::load(&path)?;
+    if let Some(refusal) = history.check(day, part, &answer) {
+        return Err(format!("not submitting {} for day {} part {}: {}", answer, day, part, refusal).into());
+    }
+    let client = Client::from_env()?;
+    let attempt = submit::submit(&client, day, part, &answer)?;
+    let outcome = attempt.outcome;
+    println!("day {} part {}: {} {}", day, part, answer, outcome);
+    println!("{}", attempt.message);
+    history.record(attempt);
+    history.save(&path)?;
+    if outcome != Outcome::Correct {
+        return Err(format!("answer was not accepted ({})", outcome).into());
+    }
+    Ok(())
+}
+
+/// Rebuild and run a day and its example tests, printing how the answers moved since `previous`
+fn rerun(root: &Path, day: u32, previous: &[(Part, String)]) -> Vec<(Part, String)> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    // A separate target directory so the build never replaces the running watcher's binary
+    let target_dir = root.join("target").join("watch");
+    let cargo_command = |subcommand: &str, args: &[&str]| {
+        Command::new(&cargo).current_dir(root).arg(subcommand).arg("--target-dir").arg(&target_dir).args(args).output()
+    };
+    // Pin the format and parts so `aoc22.toml` can't change the output `parse_answers` reads
+    let run_args = ["--quiet", "--bin", "aoc22", "--", "run", &day.to_string(), "--format", "text", "--part", "both"];
+    let output = match cargo_command("run", &run_args) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("error: could not run {}: {}", cargo, e);
+            return previous.to_vec();
+        },
+    };
+    if !output.status.success() {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        return previous.to_vec();
+    }
+    let answers = watch::parse_answers(&String::from_utf8_lossy(&output.stdout));
+    for line in watch::diff_answers(previous, &answers) {
+        println!("{}", line);
+    }
+
+    let test_name = format!("test_{}_examples", scaffold::module_name(day));
+    match cargo_command("test", &["--quiet", "--lib", &test_name]) {
+        Ok(output) if output.status.success() => println!("examples: ok"),
+        Ok(output) => {
+            println!("examples: FAILED");
+            let prefix = format!("day {} example", day);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let failures: Vec<&str> = stdout.lines().filter(|line| line.starts_with(&prefix)).collect();
+            if failures.is_empty() {
+                eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            }
+            for failure in failures {
+                println!("  {}", failure);
+            }
+        },
+        Err(e) => eprintln!("error: could not run {}: {}", cargo, e),
+    }
+    answers
+}
+
+fn watch_day(day: u32, interval: Duration) -> Result<(), CliError> {
+    let root = scaffold::project_root();
+    let paths = watch::watched_paths(&root, day);
+    println!("watching day {} every {:?}, Ctrl-C to stop", day, interval);
+    let mut snapshot = Snapshot::take(&paths);
+    let mut answers = rerun(&root, day, &[]);
+    loop {
+        thread::sleep(interval);
+        let current = Snapshot::take(&paths);
+        let changed = current.changes_since(&snapshot);
+        if changed.is_empty() {
+            continue;
+        }
+        snapshot = current;
+        let names: Vec<String> = changed
+            .iter()
+            .map(|path| path.strip_prefix(&root).unwrap_or(path).display().to_string())
+            .collect();
+        println!("\nchanged: {}", names.join(", "));
+        answers = rerun(&root, day, &answers);
+    }
+}
+
+/// The given seed, or one from the clock that is printed so the run can be repeated
+fn seed_or_random(seed: Option<u64>) -> u64 {
+    seed.unwrap_or_else(|| {
+        let seed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64;
+        eprintln!("seed: {}", seed);
+        seed
+    })
+}
+
+fn generate(day: u32, seed: Option<u64>, size: Option<u64>, params: BTreeMap<String, u64>) -> Result<(), CliError> {
+    let seed = seed_or_random(seed);
+    let input = gen::generate(find_solution(day).unwrap(), Generator::new(seed, size, params))?;
+    print!("{}", input);
+    Ok(())
+}
+
+fn anonymize_day(day: u32, input: Option<InputSource>, seed: Option<u64>) -> Result<(), CliError> {
+    let solution = configured(find_solution(day).unwrap());
+    let input = input.unwrap_or(InputSource::Day(day));
+    // Parse first so a bad input gets the usual diagnostic
+    let (content, _) = load(solution.as_ref(), &input)?;
+    let anonymized = anonymize::anonymize(solution.as_ref(), &content, seed_or_random(seed))?;
+    print!("{}", anonymized.input);
+    for line in anonymized.report() {
+        eprintln!("{}", line);
+    }
+    if anonymized.preserved() {
+        eprintln!("answers preserved");
+    } else {
+        eprintln!("answers changed");
+    }
+    Ok(())
+}
+
+fn diff(selection: Selection, seeds: u64) -> Result<(), CliError> {
+    let Some(python) = differential::find_python() else {
+        println!("no Python interpreter found (install python3 or set {}), skipping", PYTHON_VAR);
+        return Ok(());
+    };
+    let root = scaffold::project_root();
+    let mut disagreements = 0;
+    for solution in selection.solutions().iter().map(Box::as_ref) {
+        let scripts = differential::references(&root, solution.day());
+        if scripts.is_empty() {
+            continue;
+        }
+        let inputs = differential::inputs(solution, seeds);
+        for script in scripts {
+            let report = differential::check_script(&python, solution, &script, &inputs);
+            let name = script.strip_prefix(&root).unwrap_or(&script).display().to_string();
+            println!("day {} against {}: {} agree, {} disagree", solution.day(), name, report.agreed.len(), report.disagreed.len());
+            for (input, reason) in &report.skipped {
+                println!("  skipped {}: {}", input, reason);
+            }
+            if let Some(counterexample) = report.counterexample {
+                disagreements += 1;
+                println!("  disagrees on {}, minimized to:", counterexample.source);
+                for line in counterexample.input.split_inclusive('\n') {
+                    println!("    {:?}", line);
+                }
+                for (part, answer) in &counterexample.comparison.answers {
+                    let verdict = if counterexample.comparison.disagreeing.contains(part) { "not in reference output" } else { "ok" };
+                    println!("  part {}: {} ({})", part, answer, verdict);
+                }
+                println!("  reference printed:");
+                for line in counterexample.comparison.output.lines() {
+                    println!("    {}", line);
+                }
+            }
+        }
+    }
+    if disagreements > 0 {
+        return Err(format!("{} reference(s) disagree", disagreements).into());
+    }
+    Ok(())
+}
+
+fn write_report(output: Option<PathBuf>, html: Option<PathBuf>, redact: bool) -> Result<(), CliError> {
+    let rows = report::collect(&scaffold::project_root(), &AnswerDb::load(&answers::default_path())?, config::get());
+    let markdown = report::markdown(&rows, redact);
+    let write = |path: &Path, content: &str| fs::write(path, content).map_err(|e| format!("{}: {}", path.display(), e));
+    match output {
+        Some(path) => {
+            write(&path, &markdown)?;
+            println!("wrote {}", path.display());
+        },
+        None => print!("{}", markdown),
+    }
+    if let Some(path) = html {
+        write(&path, &report::html(&rows, redact))?;
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
 fn main() {
-    println!("Hello, world!");
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    log::init(take_verbosity(&mut args));
+    let config = match Config::load(&scaffold::project_root()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        },
+    };
+    config::init(config);
+    let cli = match parse_args(&args, config::get()) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        },
+    };
+    let result = match cli {
+        Cli::Run { selection, options } => run(selection, options),
+        Cli::Bench { selection, iterations, format } => bench(selection, iterations, format),
+        Cli::Verify { selection, answers, record } => verify(selection, answers, record),
+        Cli::New { day, title } => new_day(day, &title),
+        Cli::Fetch { day } => fetch(day),
+        Cli::Submit { day, part, history } => submit(day, part, history),
+        Cli::Watch { day, interval } => watch_day(day, interval),
+        Cli::Gen { day, seed, size, params } => generate(day, seed, size, params),
+        Cli::Anonymize { day, input, seed } => anonymize_day(day, input, seed),
+        Cli::Diff { selection, seeds } => diff(selection, seeds),
+        Cli::Report { output, html, redact } => write_report(output, html, redact),
+        Cli::List => {
+            for solution in SOLUTIONS.iter() {
+                println!("day {}: {}", solution.day(), solution.title());
+            }
+            Ok(())
+        },
+        Cli::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+    };
+    match result {
+        Ok(()) => {},
+        Err(CliError::Message(message)) => {
+            eprintln!("error: {}", message);
+            process::exit(1);
+        },
+        Err(CliError::Parse { error, source }) => {
+            eprint!("{}", error.render(&source));
+            process::exit(1);
+        },
+    }
+}
+
+#[test]
+fn test_parse_run_args() {
+    let args = |s: &str| s.split(' ').map(String::from).collect::<Vec<String>>();
+    let parse = |s: &str| parse_args(&args(s), &Config::default());
+    let defaults = RunOptions::default;
+    assert_eq!(parse("run 7"), Ok(Cli::Run { selection: Selection::Day(7), options: defaults() }));
+    assert_eq!(
+        parse("run 7 --part 2"),
+        Ok(Cli::Run { selection: Selection::Day(7), options: RunOptions { part: Some(Part::Two), ..defaults() } })
+    );
+    assert_eq!(parse("run all"), Ok(Cli::Run { selection: Selection::All, options: defaults() }));
+    assert_eq!(
+        parse("run 1 --input -"),
+        Ok(Cli::Run { selection: Selection::Day(1), options: RunOptions { input: Some(InputSource::Stdin), ..defaults() } })
+    );
+    assert_eq!(
+        parse("run all --format json --debug"),
+        Ok(Cli::Run { selection: Selection::All, options: RunOptions { format: Format::Json, debug: true, ..defaults() } })
+    );
+    assert!(parse("run all --input -").is_err());
+    assert_eq!(
+        parse("run all -j 4"),
+        Ok(Cli::Run { selection: Selection::All, options: RunOptions { jobs: Some(4), ..defaults() } })
+    );
+    assert!(parse("run 3 --jobs 2").is_err());
+    assert_eq!(parse("bench all -n 5 --format csv"), Ok(Cli::Bench { selection: Selection::All, iterations: 5, format: Format::Csv }));
+    assert!(parse("bench 7 -n 0").is_err());
+    assert_eq!(parse("verify --record"), Ok(Cli::Verify { selection: Selection::All, answers: None, record: true }));
+    assert_eq!(parse("new 9"), Ok(Cli::New { day: 9, title: "Day 9".to_string() }));
+    assert!(parse("new 26").is_err());
+    assert_eq!(parse("fetch 12"), Ok(Cli::Fetch { day: 12 }));
+    assert_eq!(parse("submit 5 2"), Ok(Cli::Submit { day: 5, part: Part::Two, history: None }));
+    assert!(parse("submit 5").is_err());
+    assert_eq!(parse("watch 7 --interval 100"), Ok(Cli::Watch { day: 7, interval: Duration::from_millis(100) }));
+    assert_eq!(
+        parse("gen 6 --seed 3 --param marker=57 --param message=300"),
+        Ok(Cli::Gen {
+            day: 6,
+            seed: Some(3),
+            size: None,
+            params: BTreeMap::from([("marker".to_string(), 57), ("message".to_string(), 300)]),
+        })
+    );
+    assert!(parse("gen 6 --param marker").is_err());
+    assert_eq!(
+        parse("anonymize 7 -i - --seed 1"),
+        Ok(Cli::Anonymize { day: 7, input: Some(InputSource::Stdin), seed: Some(1) })
+    );
+    assert_eq!(parse("diff 1 --seeds 10"), Ok(Cli::Diff { selection: Selection::Day(1), seeds: 10 }));
+    assert_eq!(
+        parse("report --redact --html out.html"),
+        Ok(Cli::Report { output: None, html: Some(PathBuf::from("out.html")), redact: true })
+    );
+    assert_eq!(parse("list"), Ok(Cli::List));
+    assert!(parse("run 42").is_err());
+    let config = Config { format: Some(Format::Csv), part: Some(Part::One), bench_iterations: Some(3), ..Config::default() };
+    assert_eq!(
+        parse_args(&args("run 7 -f text"), &config),
+        Ok(Cli::Run { selection: Selection::Day(7), options: RunOptions { part: Some(Part::One), ..defaults() } })
+    );
+    assert_eq!(parse_args(&args("bench 7"), &config), Ok(Cli::Bench { selection: Selection::Day(7), iterations: 3, format: Format::Csv }));
+    let mut verbose = args("-v run 6 -vv");
+    assert_eq!(take_verbosity(&mut verbose), 3);
+    assert_eq!(verbose, args("run 6"));
+    assert!(parse("run 7 --part 3").is_err());
+    assert_eq!(
+        parse_args(&args("run 7 --part both"), &config),
+        Ok(Cli::Run { selection: Selection::Day(7), options: RunOptions { part: None, format: Format::Csv, ..defaults() } })
+    );
 }
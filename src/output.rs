@@ -0,0 +1,128 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{Answer, Part};
+
+/// Output format for command results
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Csv,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format '{}', expected text, csv or json", s)),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Csv => write!(f, "csv"),
+            Format::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Quote a string as a JSON string literal
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Quote a CSV field if it contains a separator, quote or newline
+pub fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Answer to one part of one day, as reported by `aoc22 run`
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Answer,
+    pub duration: Duration,
+    /// Named details about the parsed input, empty unless requested
+    pub debug: Vec<(&'static str, String)>,
+}
+
+impl PartResult {
+    pub const CSV_HEADER: &'static str = "day,part,answer,duration_ns";
+
+    /// Single-line JSON object
+    pub fn to_json(&self) -> String {
+        let mut out = format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"duration_ns\":{}",
+            self.day,
+            self.part,
+            self.answer.to_json(),
+            self.duration.as_nanos(),
+        );
+        if !self.debug.is_empty() {
+            let fields: Vec<String> = self.debug
+                .iter()
+                .map(|(key, value)| format!("{}:{}", json_string(key), json_string(value)))
+                .collect();
+            out += &format!(",\"debug\":{{{}}}", fields.join(","));
+        }
+        out.push('}');
+        out
+    }
+
+    pub fn to_csv(&self) -> String {
+        format!("{},{},{},{}", self.day, self.part, csv_field(&self.answer.to_string()), self.duration.as_nanos())
+    }
+}
+
+#[test]
+fn test_part_result_json() {
+    let mut result = PartResult {
+        day: 5,
+        part: Part::One,
+        answer: Answer::from("CMZ"),
+        duration: Duration::from_nanos(1500),
+        debug: Vec::new(),
+    };
+    assert_eq!(result.to_json(), "{\"day\":5,\"part\":1,\"answer\":\"CMZ\",\"duration_ns\":1500}");
+    result.answer = Answer::from(24000u32);
+    result.debug = vec![("stacks", "3".to_string())];
+    assert_eq!(result.to_json(), "{\"day\":5,\"part\":1,\"answer\":24000,\"duration_ns\":1500,\"debug\":{\"stacks\":\"3\"}}");
+    assert_eq!(Answer::from(u64::MAX), Answer::Text("18446744073709551615".to_string()));
+    assert_eq!(Answer::from(i64::MAX as u64), Answer::Number(i64::MAX));
+}
+
+#[test]
+fn test_escaping() {
+    assert_eq!(json_string("a \"b\"\n"), "\"a \\\"b\\\"\\n\"");
+    assert_eq!(csv_field("plain"), "plain");
+    assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+}
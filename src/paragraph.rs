@@ -0,0 +1,111 @@
+//! Groups of lines separated by blank lines, like day 1's elves and day 5's drawing.
+//!
+//! A line holding only whitespace, including a stray `\r`, counts as blank. Runs of blank lines
+//! separate just like one, and the last group ends at the end of the input whether or not a
+//! newline follows it.
+
+use std::io::{self, BufRead};
+use std::iter::Enumerate;
+use std::str::Lines;
+
+/// One group of consecutive non-blank lines
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Paragraph<L> {
+    /// Line number of the first line, from 1
+    pub line: usize,
+    pub lines: Vec<L>,
+}
+
+impl<L: AsRef<str>> Paragraph<L> {
+    /// Lines with their line numbers
+    pub fn numbered(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.lines.iter().enumerate().map(move |(i, line)| (self.line + i, line.as_ref()))
+    }
+
+    /// Line number of the last line
+    pub fn last_line(&self) -> usize {
+        self.line + self.lines.len() - 1
+    }
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Paragraphs of a string, borrowing its lines
+pub struct Paragraphs<'a> {
+    lines: Enumerate<Lines<'a>>,
+}
+
+pub fn paragraphs(text: &str) -> Paragraphs<'_> {
+    Paragraphs { lines: text.lines().enumerate() }
+}
+
+impl<'a> Iterator for Paragraphs<'a> {
+    type Item = Paragraph<&'a str>;
+
+    fn next(&mut self) -> Option<Paragraph<&'a str>> {
+        let mut paragraph: Option<Paragraph<&'a str>> = None;
+        for (i, line) in self.lines.by_ref() {
+            if is_blank(line) {
+                if paragraph.is_some() {
+                    break;
+                }
+                continue;
+            }
+            paragraph.get_or_insert_with(|| Paragraph { line: i + 1, lines: Vec::new() }).lines.push(line);
+        }
+        paragraph
+    }
+}
+
+/// Paragraphs read one at a time, so only the current one is held in memory
+pub struct ReadParagraphs<R> {
+    reader: R,
+    line_number: usize,
+}
+
+pub fn read_paragraphs<R: BufRead>(reader: R) -> ReadParagraphs<R> {
+    ReadParagraphs { reader, line_number: 0 }
+}
+
+impl<R: BufRead> Iterator for ReadParagraphs<R> {
+    type Item = io::Result<Paragraph<String>>;
+
+    fn next(&mut self) -> Option<io::Result<Paragraph<String>>> {
+        let mut paragraph: Option<Paragraph<String>> = None;
+        loop {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return paragraph.map(Ok),
+                Ok(_) => self.line_number += 1,
+                Err(error) => return Some(Err(error)),
+            }
+            if is_blank(&line) {
+                if paragraph.is_some() {
+                    return paragraph.map(Ok);
+                }
+                continue;
+            }
+            line.truncate(line.trim_end_matches(['\n', '\r']).len());
+            let line_number = self.line_number;
+            paragraph.get_or_insert_with(|| Paragraph { line: line_number, lines: Vec::new() }).lines.push(line);
+        }
+    }
+}
+
+#[test]
+fn test_paragraphs() {
+    let text = "\n1\n2\r\n\r\n \n3\n\n\n4";
+    let expected = vec![
+        Paragraph { line: 2, lines: vec!["1", "2"] },
+        Paragraph { line: 6, lines: vec!["3"] },
+        Paragraph { line: 9, lines: vec!["4"] },
+    ];
+    assert_eq!(paragraphs(text).collect::<Vec<_>>(), expected);
+    let read: Vec<Paragraph<String>> = read_paragraphs(text.as_bytes()).collect::<io::Result<_>>().unwrap();
+    assert_eq!(read.iter().map(|p| (p.line, p.lines.join(","))).collect::<Vec<_>>(), vec![(2, "1,2".to_string()), (6, "3".to_string()), (9, "4".to_string())]);
+    assert_eq!(expected[0].numbered().collect::<Vec<_>>(), vec![(2, "1"), (3, "2")]);
+    assert_eq!(expected[0].last_line(), 3);
+    assert_eq!(paragraphs("\n \n").count(), 0);
+}
@@ -0,0 +1,60 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::thread;
+
+/// Message carried by a panic payload
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Run `work` on every item using up to `jobs` threads
+///
+/// Results come back in the order of `items`. A panic only fails its own item, as an
+/// `Err` holding the panic message.
+pub fn run_pool<T, R, F>(jobs: usize, items: Vec<T>, work: F) -> Vec<Result<R, String>>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let count = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results = Mutex::new(Vec::with_capacity(count));
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, count.max(1)) {
+            scope.spawn(|| loop {
+                // Take the lock only to pick the next item, never while working on it
+                let next = queue.lock().unwrap().next();
+                let Some((index, item)) = next else {
+                    break;
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| work(item))).map_err(panic_message);
+                results.lock().unwrap().push((index, result));
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[test]
+fn test_pool_isolates_panics() {
+    let results = run_pool(3, (1..=6).collect(), |n: u32| {
+        if n == 4 {
+            panic!("bad day {}", n);
+        }
+        n * 10
+    });
+    assert_eq!(
+        results,
+        vec![Ok(10), Ok(20), Ok(30), Err("bad day 4".to_string()), Ok(50), Ok(60)]
+    );
+}
@@ -0,0 +1,202 @@
+//! Results page for `aoc22 report`, in Markdown or as a standalone HTML page.
+//!
+//! A part counts as solved when its answer matches the one recorded in `answers.toml` for the same
+//! input. Lines of code are measured on the solver module `src/dayNN.rs` rather than `src/bin/dayN.rs`,
+//! since the binaries are now thin wrappers around the [`Solution`](crate::Solution) implementations.
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::answers::{input_hash, AnswerDb, Verdict};
+use crate::client::YEAR;
+use crate::config::Config;
+use crate::input::InputSource;
+use crate::pool;
+use crate::scaffold::module_name;
+use crate::{Answer, DynSolution, Part, SOLUTIONS};
+
+/// One day's line in the report
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayRow {
+    pub day: u32,
+    pub title: &'static str,
+    pub answers: Vec<(Part, Answer)>,
+    /// Parts whose answer matches `answers.toml`
+    pub solved: usize,
+    /// Parse and both parts together
+    pub runtime: Option<Duration>,
+    /// Lines of code in the day's solver module, `src/dayNN.rs`
+    pub loc: Option<usize>,
+    /// Why the day couldn't be run
+    pub error: Option<String>,
+}
+
+/// Lines that are neither blank nor only a `//` comment
+pub fn count_loc(source: &str) -> usize {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .count()
+}
+
+/// Run a day on its input, check it against `answers` and measure its solver module under `root`
+pub fn day_row(solution: &dyn DynSolution, root: &Path, answers: &AnswerDb) -> DayRow {
+    let loc = fs::read_to_string(root.join("src").join(format!("{}.rs", module_name(solution.day()))))
+        .ok()
+        .map(|source| count_loc(&source));
+    let mut row = DayRow { day: solution.day(), title: solution.title(), answers: Vec::new(), solved: 0, runtime: None, loc, error: None };
+    let content = match InputSource::Day(solution.day()).read() {
+        Ok(content) => content,
+        Err(error) => {
+            row.error = Some(error.to_string());
+            return row;
+        },
+    };
+    let start = Instant::now();
+    match solution.parse(&content) {
+        Ok(parsed) => {
+            row.answers = Part::both().into_iter().map(|part| (part, solution.solve(parsed.as_ref(), part))).collect();
+            row.runtime = Some(start.elapsed());
+            let hash = input_hash(&content);
+            row.solved = row
+                .answers
+                .iter()
+                .filter(|(part, answer)| answers.check(row.day, *part, &hash, &answer.to_string()) == Verdict::Match)
+                .count();
+        },
+        Err(error) => row.error = Some(error.to_string()),
+    }
+    row
+}
+
+/// Rows for every solved day with its params from `config`, one at a time so runtimes don't compete
+pub fn collect(root: &Path, answers: &AnswerDb, config: &Config) -> Vec<DayRow> {
+    let results = pool::run_pool(1, SOLUTIONS.to_vec(), |solution| {
+        let solution = solution.with_params(&|name| config.param(solution.day(), name));
+        day_row(solution.as_ref(), root, answers)
+    });
+    SOLUTIONS
+        .iter()
+        .zip(results)
+        .map(|(solution, result)| {
+            result.unwrap_or_else(|panic| DayRow {
+                day: solution.day(),
+                title: solution.title(),
+                answers: Vec::new(),
+                solved: 0,
+                runtime: None,
+                loc: None,
+                error: Some(format!("panicked: {}", panic)),
+            })
+        })
+        .collect()
+}
+
+/// Cells of a row: day, title, parts solved, both answers, runtime and the solver's lines of code
+fn cells(row: &DayRow, redact: bool) -> Vec<String> {
+    let answer = |part: Part| match row.answers.iter().find(|(p, _)| *p == part) {
+        Some(_) if redact => "(redacted)".to_string(),
+        Some((_, answer)) => answer.to_string(),
+        None => "-".to_string(),
+    };
+    vec![
+        row.day.to_string(),
+        row.title.to_string(),
+        format!("{}/2", row.solved),
+        answer(Part::One),
+        answer(Part::Two),
+        row.runtime.map_or("-".to_string(), |runtime| format!("{:.2?}", runtime)),
+        row.loc.map_or("-".to_string(), |loc| loc.to_string()),
+    ]
+}
+
+const HEADINGS: [&str; 7] = ["Day", "Title", "Parts", "Part 1", "Part 2", "Runtime", "Solver LOC"];
+
+fn summary(rows: &[DayRow]) -> String {
+    let stars: usize = rows.iter().map(|row| row.solved).sum();
+    format!("{} of 25 days solved, {} stars.", rows.iter().filter(|row| row.solved == 2).count(), stars)
+}
+
+pub fn markdown(rows: &[DayRow], redact: bool) -> String {
+    let escape = |cell: &str| cell.replace('|', "\\|");
+    let mut out = format!("# Advent of Code {}\n\n{}\n\n", YEAR, summary(rows));
+    out += &format!("| {} |\n", HEADINGS.join(" | "));
+    out += &format!("|{}\n", "---|".repeat(HEADINGS.len()));
+    for row in rows {
+        let cells: Vec<String> = cells(row, redact).iter().map(|cell| escape(cell)).collect();
+        out += &format!("| {} |\n", cells.join(" | "));
+    }
+    let errors: Vec<&DayRow> = rows.iter().filter(|row| row.error.is_some()).collect();
+    if !errors.is_empty() {
+        out += "\n## Problems\n\n";
+        for row in errors {
+            let error = row.error.as_deref().unwrap_or_default().split_whitespace().collect::<Vec<_>>().join(" ");
+            out += &format!("- Day {}: {}\n", row.day, error);
+        }
+    }
+    out
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Standalone page with the same content as [`markdown`]
+pub fn html(rows: &[DayRow], redact: bool) -> String {
+    let mut out = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out += &format!("<title>Advent of Code {}</title>\n", YEAR);
+    out += "<style>\nbody { font-family: sans-serif; margin: 2em; }\ntable { border-collapse: collapse; }\n";
+    out += "th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; }\n</style>\n</head>\n<body>\n";
+    out += &format!("<h1>Advent of Code {}</h1>\n<p>{}</p>\n<table>\n<tr>", YEAR, summary(rows));
+    for heading in HEADINGS {
+        out += &format!("<th>{}</th>", heading);
+    }
+    out += "</tr>\n";
+    for row in rows {
+        out += "<tr>";
+        for cell in cells(row, redact) {
+            out += &format!("<td>{}</td>", escape_html(&cell));
+        }
+        out += "</tr>\n";
+    }
+    out += "</table>\n";
+    let errors: Vec<&DayRow> = rows.iter().filter(|row| row.error.is_some()).collect();
+    if !errors.is_empty() {
+        out += "<h2>Problems</h2>\n<ul>\n";
+        for row in errors {
+            out += &format!("<li>Day {}: {}</li>\n", row.day, escape_html(row.error.as_deref().unwrap_or_default()));
+        }
+        out += "</ul>\n";
+    }
+    out += "</body>\n</html>\n";
+    out
+}
+
+#[test]
+fn test_markdown_report() {
+    assert_eq!(count_loc("use std::fs;\n\n// comment\nfn main() {\n    // note\n}\n"), 3);
+    let rows = vec![
+        DayRow {
+            day: 1,
+            title: "Calorie Counting",
+            answers: vec![(Part::One, Answer::Number(24000)), (Part::Two, Answer::Number(45000))],
+            solved: 2,
+            runtime: Some(Duration::from_micros(1500)),
+            loc: Some(12),
+            error: None,
+        },
+        DayRow { day: 2, title: "Rock | Paper", answers: Vec::new(), solved: 0, runtime: None, loc: None, error: Some("no input\nfound".to_string()) },
+        DayRow { day: 3, title: "Unchecked", answers: vec![(Part::One, Answer::Number(7))], solved: 0, runtime: None, loc: None, error: None },
+    ];
+    let report = markdown(&rows, false);
+    assert!(report.contains("1 of 25 days solved, 2 stars."));
+    assert!(report.contains("| Runtime | Solver LOC |\n"));
+    assert!(report.contains("| 1 | Calorie Counting | 2/2 | 24000 | 45000 | 1.50ms | 12 |\n"), "{}", report);
+    assert!(report.contains("| 2 | Rock \\| Paper | 0/2 | - | - | - | - |\n"));
+    assert!(report.contains("- Day 2: no input found\n"));
+    assert!(report.contains("| 3 | Unchecked | 0/2 | 7 | - | - | - |\n"));
+    assert!(!markdown(&rows, true).contains("24000"));
+    assert!(html(&rows, false).contains("<td>24000</td>"));
+}
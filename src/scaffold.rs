@@ -0,0 +1,229 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::input::day_dir;
+
+/// Example input every new day starts with, filled in from the puzzle description
+pub const EXAMPLE_FILE: &str = "test.txt";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// Files for the day already exist and would be overwritten
+    Exists(Vec<PathBuf>),
+    /// The day is already wired into `file`
+    AlreadyRegistered { day: u32, file: PathBuf },
+    /// `file` doesn't contain the `what` that new days get registered next to
+    MissingAnchor { file: PathBuf, what: &'static str },
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(paths) => {
+                write!(f, "refusing to overwrite existing files:")?;
+                for path in paths {
+                    write!(f, "\n    {}", path.display())?;
+                }
+                Ok(())
+            },
+            ScaffoldError::AlreadyRegistered { day, file } => {
+                write!(f, "day {} is already registered in {}", day, file.display())
+            },
+            ScaffoldError::MissingAnchor { file, what } => write!(f, "could not find {} in {}", what, file.display()),
+            ScaffoldError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+/// Root of the crate to scaffold into: the current directory when it holds the sources, otherwise the crate root
+pub fn project_root() -> PathBuf {
+    match env::current_dir() {
+        Ok(dir) if dir.join("src").join("lib.rs").is_file() => dir,
+        _ => PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+    }
+}
+
+pub fn module_name(day: u32) -> String {
+    format!("day{:02}", day)
+}
+
+pub fn struct_name(day: u32) -> String {
+    format!("Day{}", day)
+}
+
+/// Source of a new day's module, implementing [`crate::Solution`] with placeholder parts
+pub fn module_source(day: u32, title: &str) -> String {
+    format!(
+        "\
+use crate::{{Example, ParseError, Solution}};
+
+#[derive(Clone)]
+pub struct {name};
+
+impl Solution for {name} {{
+    const DAY: u32 = {day};
+    const TITLE: &'static str = {title:?};
+    const EXAMPLES: &'static [Example] = &[
+        Example {{ input: include_str!(\"../{dir}/{example}\"), part1: None, part2: None }},
+    ];
+
+    type Parsed = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, content: &str) -> Result<Vec<String>, ParseError> {{
+        Ok(content.lines().map(String::from).collect())
+    }}
+
+    fn part1(&self, lines: &Vec<String>) -> usize {{
+        lines.len()
+    }}
+
+    fn part2(&self, _lines: &Vec<String>) -> usize {{
+        0
+    }}
+}}
+",
+        name = struct_name(day),
+        day = day,
+        title = title,
+        dir = day_dir(day).display(),
+        example = EXAMPLE_FILE,
+    )
+}
+
+/// Source of a new day's standalone binary
+pub fn bin_source(day: u32) -> String {
+    format!(
+        "\
+use aoc22::{module}::{name};
+use aoc22::input::InputSource;
+use aoc22::{{ParseError, Solution}};
+
+fn main() {{
+    println!(\"Day {day}\");
+    let input = InputSource::Day({name}::DAY);
+    let content = input.read().expect(\"Something went wrong reading the file\");
+    let parsed = ParseError::or_exit({name}.parse(&content), &input.to_string());
+
+    println!(\"Part 1: {{}}\", {name}.part1(&parsed));
+    println!(\"Part 2: {{}}\", {name}.part2(&parsed));
+}}
+",
+        module = module_name(day),
+        name = struct_name(day),
+        day = day,
+    )
+}
+
+/// Day number of a line such as `pub mod day07;` when it starts with `prefix`
+fn line_day(line: &str, prefix: &str) -> Option<u32> {
+    let rest = line.trim().strip_prefix(prefix)?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// Insert `new_line` among the lines starting with `prefix`, keeping them ordered by day
+fn insert_line(text: &str, file: &Path, prefix: &str, what: &'static str, day: u32, new_line: &str) -> Result<String, ScaffoldError> {
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let mut position = None;
+    for (i, line) in lines.iter().enumerate() {
+        match line_day(line, prefix) {
+            Some(existing) if existing == day => {
+                return Err(ScaffoldError::AlreadyRegistered { day, file: file.to_path_buf() });
+            },
+            Some(existing) if existing < day => position = Some(i + 1),
+            Some(_) if position.is_none() => position = Some(i),
+            _ => {},
+        }
+    }
+    let position = position.ok_or_else(|| ScaffoldError::MissingAnchor { file: file.to_path_buf(), what })?;
+    let mut out: String = lines[..position].concat();
+    out += new_line;
+    out.push('\n');
+    out += &lines[position..].concat();
+    Ok(out)
+}
+
+/// Add a day's module and registry entry to the source of `lib.rs`
+pub fn register_in_lib(lib: &str, file: &Path, day: u32) -> Result<String, ScaffoldError> {
+    let module = module_name(day);
+    let lib = insert_line(lib, file, "pub mod day", "the day modules", day, &format!("pub mod {};", module))?;
+    let lib = insert_line(&lib, file, "&day", "the SOLUTIONS entries", day, &format!("    &{}::{},", module, struct_name(day)))?;
+    // The registry is a fixed-size array, so its length grows with it
+    let marker = "[&dyn DynSolution; ";
+    let missing = || ScaffoldError::MissingAnchor { file: file.to_path_buf(), what: "the SOLUTIONS length" };
+    let start = lib.find(marker).ok_or_else(missing)? + marker.len();
+    let end = start + lib[start..].find(']').ok_or_else(missing)?;
+    let count: usize = lib[start..end].parse().map_err(|_| missing())?;
+    Ok(format!("{}{}{}", &lib[..start], count + 1, &lib[end..]))
+}
+
+/// Add a day to the generated example tests in `examples.rs`
+pub fn register_in_examples(examples: &str, file: &Path, day: u32) -> Result<String, ScaffoldError> {
+    let module = module_name(day);
+    let entry = format!("    test_{}_examples => crate::{}::{},", module, module, struct_name(day));
+    insert_line(examples, file, "test_day", "the example_tests! entries", day, &entry)
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|source| ScaffoldError::Io { path: path.to_path_buf(), source })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|source| ScaffoldError::Io { path: path.to_path_buf(), source })
+}
+
+/// Generate and register a new day under `root`, returning the files created or changed
+///
+/// Nothing is written unless every step can succeed. An existing `day N/` directory and
+/// example file are kept as they are.
+pub fn scaffold(root: &Path, day: u32, title: &str) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let module_path = root.join("src").join(format!("{}.rs", module_name(day)));
+    let bin_path = root.join("src").join("bin").join(format!("day{}.rs", day));
+    let existing: Vec<PathBuf> = [&module_path, &bin_path].into_iter().filter(|p| p.exists()).cloned().collect();
+    if !existing.is_empty() {
+        return Err(ScaffoldError::Exists(existing));
+    }
+
+    let lib_path = root.join("src").join("lib.rs");
+    let lib = register_in_lib(&read(&lib_path)?, &lib_path, day)?;
+    let examples_path = root.join("src").join("examples.rs");
+    let examples = register_in_examples(&read(&examples_path)?, &examples_path, day)?;
+
+    let mut changed = Vec::new();
+    let dir = root.join(day_dir(day));
+    fs::create_dir_all(&dir).map_err(|source| ScaffoldError::Io { path: dir.clone(), source })?;
+    let example_path = dir.join(EXAMPLE_FILE);
+    if !example_path.exists() {
+        write(&example_path, "")?;
+        changed.push(example_path);
+    }
+    write(&module_path, &module_source(day, title))?;
+    changed.push(module_path);
+    write(&bin_path, &bin_source(day))?;
+    changed.push(bin_path);
+    write(&lib_path, &lib)?;
+    changed.push(lib_path);
+    write(&examples_path, &examples)?;
+    changed.push(examples_path);
+    Ok(changed)
+}
+
+#[test]
+fn test_register_in_lib() {
+    let lib = "pub mod day01;\npub mod day10;\n\npub static SOLUTIONS: [&dyn DynSolution; 2] = [\n    &day01::Day1,\n    &day10::Day10,\n];\n";
+    let file = Path::new("lib.rs");
+    assert_eq!(
+        register_in_lib(lib, file, 9).unwrap(),
+        "pub mod day01;\npub mod day09;\npub mod day10;\n\npub static SOLUTIONS: [&dyn DynSolution; 3] = [\n    &day01::Day1,\n    &day09::Day9,\n    &day10::Day10,\n];\n"
+    );
+    assert!(matches!(register_in_lib(lib, file, 10), Err(ScaffoldError::AlreadyRegistered { day: 10, .. })));
+    assert!(matches!(register_in_lib("", file, 3), Err(ScaffoldError::MissingAnchor { .. })));
+}
@@ -0,0 +1,242 @@
+use std::any::Any;
+use std::fmt;
+
+use crate::error::ParseError;
+use crate::gen::{Generator, Rng};
+use crate::output::json_string;
+
+/// Answer to one part of a puzzle
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Numbers beyond `i64` become text rather than wrapping
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    i64::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Number)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(i32, i64, u32, u64, u128, usize);
+
+impl Answer {
+    /// Numbers as JSON numbers, text as JSON strings
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Number(n) => n.to_string(),
+            Answer::Text(s) => json_string(s),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// Puzzle part selector
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn both() -> [Part; 2] {
+        [Part::One, Part::Two]
+    }
+
+    pub fn number(&self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Worked example from a puzzle description, with the answers it gives
+///
+/// An expected answer of `None` leaves that part unchecked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+}
+
+/// Tunable constant of a day's puzzle, settable in the config file's `[dayN]` table
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+    pub min: i64,
+    pub max: i64,
+}
+
+/// A day's solution, split into parsing and the two parts
+///
+/// Parsing happens once and both parts share the parsed input.
+pub trait Solution {
+    /// Day of the puzzle (1-25)
+    const DAY: u32;
+    /// Puzzle title as shown on the site
+    const TITLE: &'static str;
+    /// Examples from the puzzle description, checked by the generated example tests
+    const EXAMPLES: &'static [Example] = &[];
+    /// Constants the solution takes through [`configure`](Solution::configure)
+    const PARAMS: &'static [Param] = &[];
+
+    /// Parsed representation of the puzzle input
+    type Parsed: 'static;
+    type Answer1: Into<Answer>;
+    type Answer2: Into<Answer>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(&self, parsed: &Self::Parsed) -> Self::Answer1;
+    fn part2(&self, parsed: &Self::Parsed) -> Self::Answer2;
+
+    /// Optional named details about the parsed input, shown alongside answers when debugging
+    fn debug(&self, _parsed: &Self::Parsed) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Take the value of each of [`PARAMS`](Solution::PARAMS) from `param`
+    fn configure(&mut self, _param: &dyn Fn(&str) -> i64) {}
+
+    /// Random valid input for `aoc22 gen`, or `None` when the day has no generator
+    fn generate(&self, _gen: &mut Generator) -> Option<String> {
+        None
+    }
+
+    /// Structurally equivalent copy of a valid input for `aoc22 anonymize`, or `None` when the
+    /// day has no anonymizer
+    fn anonymize(&self, _input: &str, _rng: &mut Rng) -> Option<String> {
+        None
+    }
+
+    /// Answer the anonymized input should have, given the original input's, for days whose
+    /// anonymizer relabels what the answer is made of
+    fn relabel_answer(&self, _input: &str, _anonymized: &str, answer: Answer) -> Answer {
+        answer
+    }
+}
+
+/// Type-erased view of a [`Solution`] so days can be stored together in a registry
+pub trait DynSolution: Sync {
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+    fn examples(&self) -> &'static [Example];
+    fn params(&self) -> &'static [Param];
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn part1(&self, parsed: &dyn Any) -> Answer;
+    fn part2(&self, parsed: &dyn Any) -> Answer;
+    fn debug(&self, parsed: &dyn Any) -> Vec<(&'static str, String)>;
+    fn generate(&self, gen: &mut Generator) -> Option<String>;
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String>;
+    fn relabel_answer(&self, input: &str, anonymized: &str, answer: Answer) -> Answer;
+
+    /// Copy of the solution with its params taken from `param` instead of their defaults
+    fn with_params(&self, param: &dyn Fn(&str) -> i64) -> Box<dyn DynSolution>;
+
+    /// Run a single part on already parsed input
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Answer {
+        match part {
+            Part::One => self.part1(parsed),
+            Part::Two => self.part2(parsed),
+        }
+    }
+}
+
+impl<S: Solution + Clone + Sync + 'static> DynSolution for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        S::EXAMPLES
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn part1(&self, parsed: &dyn Any) -> Answer {
+        let parsed = parsed.downcast_ref::<S::Parsed>().expect("Parsed input belongs to another day");
+        Solution::part1(self, parsed).into()
+    }
+
+    fn part2(&self, parsed: &dyn Any) -> Answer {
+        let parsed = parsed.downcast_ref::<S::Parsed>().expect("Parsed input belongs to another day");
+        Solution::part2(self, parsed).into()
+    }
+
+    fn debug(&self, parsed: &dyn Any) -> Vec<(&'static str, String)> {
+        let parsed = parsed.downcast_ref::<S::Parsed>().expect("Parsed input belongs to another day");
+        Solution::debug(self, parsed)
+    }
+
+    fn generate(&self, gen: &mut Generator) -> Option<String> {
+        Solution::generate(self, gen)
+    }
+
+    fn anonymize(&self, input: &str, rng: &mut Rng) -> Option<String> {
+        Solution::anonymize(self, input, rng)
+    }
+
+    fn relabel_answer(&self, input: &str, anonymized: &str, answer: Answer) -> Answer {
+        Solution::relabel_answer(self, input, anonymized, answer)
+    }
+
+    fn with_params(&self, param: &dyn Fn(&str) -> i64) -> Box<dyn DynSolution> {
+        let mut solution = self.clone();
+        solution.configure(param);
+        Box::new(solution)
+    }
+}
@@ -0,0 +1,347 @@
+//! Answer submission and the history of every attempt.
+//!
+//! Attempts are kept in the same TOML subset as the answers file:
+//!
+//! ```toml
+//! [[attempt]]
+//! day = 1
+//! part = 1
+//! answer = "72718"
+//! outcome = "too_low"
+//! time = 1670000000
+//! message = "That's not the right answer; your answer is too low."
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::client::{Client, ClientError, YEAR};
+use crate::http;
+use crate::input::data_file;
+use crate::toml::{self, Value};
+use crate::Part;
+
+pub const HISTORY_FILE: &str = "submissions.toml";
+
+/// Default location of the history file, see [`data_file`]
+pub fn default_path() -> PathBuf {
+    data_file(HISTORY_FILE)
+}
+
+/// How the site judged a submitted answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted too soon after the previous attempt, not judged
+    Wait,
+    /// The part is locked or already solved, not judged
+    WrongLevel,
+    /// The response wasn't recognised
+    Unknown,
+}
+
+impl Outcome {
+    const ALL: [Outcome; 7] = [
+        Outcome::Correct,
+        Outcome::Wrong,
+        Outcome::TooHigh,
+        Outcome::TooLow,
+        Outcome::Wait,
+        Outcome::WrongLevel,
+        Outcome::Unknown,
+    ];
+
+    /// Name used in the history file
+    pub fn key(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Wait => "wait",
+            Outcome::WrongLevel => "wrong_level",
+            Outcome::Unknown => "unknown",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Outcome> {
+        Outcome::ALL.into_iter().find(|outcome| outcome.key() == key)
+    }
+
+    /// Whether the site rejected the answer itself, so it must never be sent again
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key().replace('_', " "))
+    }
+}
+
+/// Text of the `<article>` in the site's response, without markup
+pub fn response_message(html: &str) -> String {
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {},
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub fn parse_outcome(message: &str) -> Outcome {
+    if message.contains("That's the right answer") {
+        Outcome::Correct
+    } else if message.contains("your answer is too high") {
+        Outcome::TooHigh
+    } else if message.contains("your answer is too low") {
+        Outcome::TooLow
+    } else if message.contains("That's not the right answer") {
+        Outcome::Wrong
+    } else if message.contains("You gave an answer too recently") {
+        Outcome::Wait
+    } else if message.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attempt {
+    pub day: u32,
+    pub part: Part,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the Unix epoch
+    pub time: u64,
+    pub message: String,
+}
+
+/// Why an answer is not worth submitting
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    /// This exact answer was rejected before
+    KnownWrong { outcome: Outcome },
+    /// A numeric answer past one already reported as too high or too low
+    OutOfBounds { bound: String, outcome: Outcome },
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => write!(f, "already solved with {}", answer),
+            Refusal::KnownWrong { outcome } => write!(f, "this answer was already rejected ({})", outcome),
+            Refusal::OutOfBounds { bound, outcome } => write!(f, "{} was already {}", bound, outcome),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Io { path: PathBuf, source: io::Error },
+    Syntax { line: usize, message: String },
+    Client(ClientError),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            SubmitError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            SubmitError::Client(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+impl From<toml::SyntaxError> for SubmitError {
+    fn from(error: toml::SyntaxError) -> SubmitError {
+        SubmitError::Syntax { line: error.line, message: error.message }
+    }
+}
+
+impl From<ClientError> for SubmitError {
+    fn from(error: ClientError) -> SubmitError {
+        SubmitError::Client(error)
+    }
+}
+
+/// Every answer submitted so far, oldest first
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct History {
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn new() -> History {
+        History::default()
+    }
+
+    pub fn parse(text: &str) -> Result<History, SubmitError> {
+        let mut history = History::new();
+        for table in toml::parse(text)? {
+            let syntax = |line: usize, message: String| SubmitError::Syntax { line, message };
+            match (table.name.as_str(), table.array) {
+                ("attempt", true) => {},
+                ("", _) => match table.entries.first() {
+                    Some(entry) => {
+                        return Err(syntax(entry.line, format!("'{}' outside of an [[attempt]] table", entry.key)));
+                    },
+                    None => continue,
+                },
+                (name, _) => return Err(syntax(table.line, format!("unexpected table '{}'", name))),
+            }
+            let (mut day, mut part, mut answer, mut outcome, mut time, mut message) = (None, None, None, None, None, None);
+            for entry in &table.entries {
+                let invalid = || syntax(entry.line, format!("invalid {} {}", entry.key, entry.value));
+                match (entry.key.as_str(), &entry.value) {
+                    ("day", Value::Integer(n)) => day = Some(u32::try_from(*n).map_err(|_| invalid())?),
+                    ("part", Value::Integer(1)) => part = Some(Part::One),
+                    ("part", Value::Integer(2)) => part = Some(Part::Two),
+                    ("answer", Value::String(s)) => answer = Some(s.clone()),
+                    ("outcome", Value::String(s)) => outcome = Some(Outcome::from_key(s).ok_or_else(invalid)?),
+                    ("time", Value::Integer(n)) => time = Some(u64::try_from(*n).map_err(|_| invalid())?),
+                    ("message", Value::String(s)) => message = Some(s.clone()),
+                    ("day" | "part" | "answer" | "outcome" | "time" | "message", _) => return Err(invalid()),
+                    (key, _) => return Err(syntax(entry.line, format!("unknown key '{}'", key))),
+                }
+            }
+            let missing = |key: &str| syntax(table.line, format!("[[attempt]] is missing '{}'", key));
+            history.attempts.push(Attempt {
+                day: day.ok_or_else(|| missing("day"))?,
+                part: part.ok_or_else(|| missing("part"))?,
+                answer: answer.ok_or_else(|| missing("answer"))?,
+                outcome: outcome.ok_or_else(|| missing("outcome"))?,
+                time: time.unwrap_or(0),
+                message: message.unwrap_or_default(),
+            });
+        }
+        Ok(history)
+    }
+
+    /// Load the history from a file; a missing file is an empty history
+    pub fn load(path: &Path) -> Result<History, SubmitError> {
+        match fs::read_to_string(path) {
+            Ok(text) => History::parse(&text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::new()),
+            Err(source) => Err(SubmitError::Io { path: path.to_path_buf(), source }),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SubmitError> {
+        fs::write(path, self.to_toml()).map_err(|source| SubmitError::Io { path: path.to_path_buf(), source })
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    /// Serialise in submission order, which is the order that matters when reading it back
+    pub fn to_toml(&self) -> String {
+        let mut out = String::from("# Answers sent by `aoc22 submit`\n");
+        for attempt in &self.attempts {
+            out += &format!(
+                "\n[[attempt]]\nday = {}\npart = {}\nanswer = {}\noutcome = {}\ntime = {}\nmessage = {}\n",
+                attempt.day,
+                attempt.part,
+                toml::quote(&attempt.answer),
+                toml::quote(attempt.outcome.key()),
+                attempt.time,
+                toml::quote(&attempt.message),
+            );
+        }
+        out
+    }
+
+    /// Reason not to submit `answer`, judging by earlier attempts for the same day and part
+    pub fn check(&self, day: u32, part: Part, answer: &str) -> Option<Refusal> {
+        let attempts: Vec<&Attempt> = self.attempts.iter().filter(|a| a.day == day && a.part == part).collect();
+        if let Some(solved) = attempts.iter().find(|a| a.outcome == Outcome::Correct) {
+            return Some(Refusal::AlreadySolved { answer: solved.answer.clone() });
+        }
+        if let Some(known) = attempts.iter().find(|a| a.answer == answer && a.outcome.is_wrong()) {
+            return Some(Refusal::KnownWrong { outcome: known.outcome });
+        }
+        let value = answer.parse::<i64>().ok()?;
+        attempts.iter().find_map(|a| {
+            let bound = a.answer.parse::<i64>().ok()?;
+            let past = match a.outcome {
+                Outcome::TooHigh => value >= bound,
+                Outcome::TooLow => value <= bound,
+                _ => false,
+            };
+            past.then(|| Refusal::OutOfBounds { bound: a.answer.clone(), outcome: a.outcome })
+        })
+    }
+}
+
+/// Post an answer to the site and return the attempt, which the caller should record
+pub fn submit(client: &Client, day: u32, part: Part, answer: &str) -> Result<Attempt, SubmitError> {
+    let level = part.to_string();
+    let body = http::form_encode(&[("level", &level), ("answer", answer)]);
+    let response = client.send("POST", &format!("/{}/day/{}/answer", YEAR, day), Some(&body))?;
+    if response.status != 200 {
+        // The site redirects to the puzzle page when the session is invalid
+        let message = match response.header("Location") {
+            Some(location) => format!("redirected to {}", location),
+            None => response_message(&response.body),
+        };
+        return Err(ClientError::Rejected { status: response.status, message }.into());
+    }
+    let message = response_message(&response.body);
+    Ok(Attempt {
+        day,
+        part,
+        answer: answer.to_string(),
+        outcome: parse_outcome(&message),
+        time: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+        message,
+    })
+}
+
+#[test]
+fn test_submit_and_refuse_known_wrong() {
+    let (client, server) = crate::client::serve_client(
+        "HTTP/1.1 200 OK\r\n\r\n<html><main><article><p>That's not the right answer; \
+         your answer is too high.  Please wait one minute.</p></article></main></html>",
+        std::env::temp_dir().join(format!("aoc22-submit-{}", std::process::id())),
+    );
+    let attempt = submit(&client, 1, Part::Two, "213089").unwrap();
+    let request = server.join().unwrap();
+    assert!(request.starts_with("POST /2022/day/1/answer HTTP/1.0\r\n"));
+    assert!(request.ends_with("\r\n\r\nlevel=2&answer=213089"));
+    assert_eq!(attempt.outcome, Outcome::TooHigh);
+    assert_eq!(attempt.message, "That's not the right answer; your answer is too high. Please wait one minute.");
+
+    let mut history = History::new();
+    history.record(attempt);
+    let history = History::parse(&history.to_toml()).unwrap();
+    assert_eq!(history.check(1, Part::Two, "213089"), Some(Refusal::KnownWrong { outcome: Outcome::TooHigh }));
+    assert!(matches!(history.check(1, Part::Two, "300000"), Some(Refusal::OutOfBounds { .. })));
+    assert_eq!(history.check(1, Part::Two, "200000"), None);
+    assert_eq!(history.check(1, Part::One, "213089"), None);
+    let _ = fs::remove_file(&client.stamp_path);
+}
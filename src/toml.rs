@@ -0,0 +1,230 @@
+//! Just enough TOML for the files this crate reads and writes.
+//!
+//! Supports `[table]` and `[[array-of-tables]]` headers, bare keys, and basic strings,
+//! integers, booleans and single-line arrays of those as values, with `#` comments. Keys before the first header
+//! belong to a root table with an empty name.
+
+use std::fmt;
+
+use crate::output::json_string;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "a string",
+            Value::Integer(_) => "an integer",
+            Value::Boolean(_) => "a boolean",
+            Value::Array(_) => "an array",
+        }
+    }
+}
+
+/// Formats the value as it would be written in a file
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(s) => write!(f, "{}", quote(s)),
+            Value::Integer(n) => write!(f, "{}", n),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::Array(values) => {
+                let values: Vec<String> = values.iter().map(Value::to_string).collect();
+                write!(f, "[{}]", values.join(", "))
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// 1-based line the key was defined on
+    pub line: usize,
+    pub key: String,
+    pub value: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table {
+    /// Empty for keys before the first header
+    pub name: String,
+    /// Whether the header was `[[name]]`
+    pub array: bool,
+    /// 1-based line of the header, 0 for the root table
+    pub line: usize,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
+/// Quote a string as a TOML basic string
+pub fn quote(s: &str) -> String {
+    // JSON string escapes are a subset of TOML's
+    json_string(s)
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Parse a basic string at the start of `value`, returning its contents and the rest of the text
+fn parse_string(value: &str) -> Option<(String, &str)> {
+    let inner = value.strip_prefix('"')?;
+    let mut out = String::new();
+    let mut chars = inner.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next()?.1 {
+                '"' => out.push('"'),
+                '\\' => out.push('\\'),
+                'n' => out.push('\n'),
+                'r' => out.push('\r'),
+                't' => out.push('\t'),
+                'u' => {
+                    let hex: String = (0..4).filter_map(|_| chars.next().map(|(_, c)| c)).collect();
+                    out.push(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)?);
+                },
+                _ => return None,
+            },
+            '"' => return Some((out, &inner[i + 1..])),
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+/// Parse a string, integer or boolean at the start of `text`, ending at any of `terminators`
+fn parse_scalar<'a>(text: &'a str, terminators: &[char]) -> Result<(Value, &'a str), String> {
+    if text.starts_with('"') {
+        let (s, rest) = parse_string(text).ok_or_else(|| format!("invalid string {}", text))?;
+        return Ok((Value::String(s), rest));
+    }
+    let end = text.find(terminators).unwrap_or(text.len());
+    let token = text[..end].trim();
+    let value = match token {
+        "true" => Value::Boolean(true),
+        "false" => Value::Boolean(false),
+        _ => Value::Integer(token.replace('_', "").parse::<i64>().map_err(|_| format!("invalid value '{}'", token))?),
+    };
+    Ok((value, &text[end..]))
+}
+
+/// Parse a single-line array at the start of `text`, returning it and the rest of the text
+fn parse_array(text: &str) -> Result<(Value, &str), String> {
+    let mut rest = text.strip_prefix('[').unwrap_or(text).trim_start();
+    let mut values = Vec::new();
+    loop {
+        if let Some(after) = rest.strip_prefix(']') {
+            return Ok((Value::Array(values), after));
+        }
+        if rest.is_empty() || rest.starts_with('#') {
+            return Err("unclosed array".to_string());
+        }
+        let (value, after) = parse_scalar(rest, &[',', ']', '#'])?;
+        values.push(value);
+        rest = after.trim_start();
+        if let Some(after) = rest.strip_prefix(',') {
+            rest = after.trim_start();
+        } else if !rest.starts_with(']') {
+            return Err(format!("expected ',' or ']' in array, found '{}'", rest));
+        }
+    }
+}
+
+fn parse_value(text: &str) -> Result<Value, String> {
+    let (value, rest) = if text.starts_with('[') {
+        parse_array(text)?
+    } else {
+        parse_scalar(text, &['#'])?
+    };
+    let rest = rest.trim();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err(format!("unexpected '{}' after value", rest));
+    }
+    Ok(value)
+}
+
+/// Parse a document into its tables, in order, starting with the root table
+pub fn parse(text: &str) -> Result<Vec<Table>, SyntaxError> {
+    let mut tables = vec![Table { name: String::new(), array: false, line: 0, entries: Vec::new() }];
+    for (i, raw_line) in text.lines().enumerate() {
+        let line_number = i + 1;
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let syntax = |message: String| SyntaxError { line: line_number, message };
+        if line.starts_with('[') {
+            let header = line.split('#').next().unwrap_or("").trim();
+            let (name, array) = match header.strip_prefix("[[").and_then(|h| h.strip_suffix("]]")) {
+                Some(name) => (name, true),
+                None => match header.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+                    Some(name) => (name, false),
+                    None => return Err(syntax(format!("invalid table header '{}'", line))),
+                },
+            };
+            let name = name.trim();
+            if !is_bare_key(name) {
+                return Err(syntax(format!("invalid table name '{}'", name)));
+            }
+            if !array && tables.iter().any(|t| t.name == name) {
+                return Err(syntax(format!("table [{}] defined twice", name)));
+            }
+            tables.push(Table { name: name.to_string(), array, line: line_number, entries: Vec::new() });
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .ok_or_else(|| syntax(format!("expected 'key = value' or a table header, found '{}'", line)))?;
+        if !is_bare_key(key) {
+            return Err(syntax(format!("invalid key '{}'", key)));
+        }
+        let value = parse_value(value).map_err(syntax)?;
+        let table = tables.last_mut().expect("root table is always present");
+        if table.entries.iter().any(|e| e.key == key) {
+            return Err(syntax(format!("'{}' defined twice", key)));
+        }
+        table.entries.push(Entry { line: line_number, key: key.to_string(), value });
+    }
+    Ok(tables)
+}
+
+#[test]
+fn test_parse_tables() {
+    let text = "top = 1\n\n[run]\nformat = \"json\" # comment\n\n[[answer]]\nday = 1\nok = true\n[[answer]]\nday = 2\n";
+    let tables = parse(text).unwrap();
+    assert_eq!(tables.len(), 4);
+    assert_eq!(tables[0].entries[0].value, Value::Integer(1));
+    assert_eq!((tables[1].name.as_str(), tables[1].array), ("run", false));
+    assert_eq!(tables[1].entries[0].value, Value::String("json".to_string()));
+    assert_eq!(tables[2].entries[1], Entry { line: 8, key: "ok".to_string(), value: Value::Boolean(true) });
+    assert_eq!(tables[3].line, 9);
+    assert_eq!(parse("[run]\nx = 1\nx = 2").unwrap_err().line, 3);
+    assert_eq!(parse("x = \"open").unwrap_err().line, 1);
+    assert_eq!(parse_string("\"a\\\"b\" rest"), Some(("a\"b".to_string(), " rest")));
+    let dirs = Value::Array(vec![Value::String("a]".to_string()), Value::Integer(2)]);
+    assert_eq!(parse_value("[ \"a]\", 2, ] # dirs"), Ok(dirs.clone()));
+    assert_eq!(dirs.to_string(), "[\"a]\", 2]");
+    assert_eq!(parse_value("[]"), Ok(Value::Array(Vec::new())));
+    assert!(parse_value("[1, 2").is_err());
+    assert!(parse_value("[1 2]").is_err());
+}
@@ -0,0 +1,109 @@
+//! Polling support for `aoc22 watch`, so no OS notification service is needed.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::input::{candidate_paths, day_dir};
+use crate::scaffold::module_name;
+use crate::Part;
+
+/// Files that affect a day's answers: its `day N/` directory, its module and binary, and its input
+pub fn watched_paths(root: &Path, day: u32) -> Vec<PathBuf> {
+    let mut paths = vec![
+        root.join(day_dir(day)),
+        root.join("src").join(format!("{}.rs", module_name(day))),
+        root.join("src").join("bin").join(format!("day{}.rs", day)),
+    ];
+    for path in candidate_paths(day) {
+        let path = if path.is_relative() { root.join(path) } else { path };
+        if !paths.iter().any(|p| path.starts_with(p)) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Modification time and size of every watched file, with directories expanded one level
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    files: BTreeMap<PathBuf, (Option<SystemTime>, u64)>,
+}
+
+impl Snapshot {
+    pub fn take(paths: &[PathBuf]) -> Snapshot {
+        let mut files = BTreeMap::new();
+        for path in paths {
+            let entries: Vec<PathBuf> = match fs::read_dir(path) {
+                Ok(dir) => dir.filter_map(|entry| entry.ok()).map(|entry| entry.path()).filter(|p| p.is_file()).collect(),
+                Err(_) => vec![path.clone()],
+            };
+            for entry in entries {
+                if let Ok(metadata) = fs::metadata(&entry) {
+                    files.insert(entry, (metadata.modified().ok(), metadata.len()));
+                }
+            }
+        }
+        Snapshot { files }
+    }
+
+    /// Files added, removed or modified since `earlier`
+    pub fn changes_since(&self, earlier: &Snapshot) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|(path, stamp)| earlier.files.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(earlier.files.keys().filter(|path| !self.files.contains_key(*path)).cloned());
+        changed.sort();
+        changed
+    }
+}
+
+/// Answers from the text output of `aoc22 run`
+pub fn parse_answers(output: &str) -> Vec<(Part, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (label, answer) = line.split_once(": ")?;
+            let part = match label {
+                "Part 1" => Part::One,
+                "Part 2" => Part::Two,
+                _ => return None,
+            };
+            Some((part, answer.to_string()))
+        })
+        .collect()
+}
+
+/// One line per part comparing the new answers with the previous run's
+pub fn diff_answers(previous: &[(Part, String)], current: &[(Part, String)]) -> Vec<String> {
+    current
+        .iter()
+        .map(|(part, answer)| match previous.iter().find(|(p, _)| p == part) {
+            Some((_, old)) if old == answer => format!("part {}: {} (unchanged)", part, answer),
+            Some((_, old)) => format!("part {}: {} -> {}", part, old, answer),
+            None => format!("part {}: {}", part, answer),
+        })
+        .collect()
+}
+
+#[test]
+fn test_snapshot_and_diff() {
+    let dir = std::env::temp_dir().join(format!("aoc22-watch-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("input.txt"), "1\n").unwrap();
+    let paths = vec![dir.clone(), dir.join("missing.rs")];
+    let before = Snapshot::take(&paths);
+    assert!(Snapshot::take(&paths).changes_since(&before).is_empty());
+    fs::write(dir.join("input.txt"), "1\n2\n").unwrap();
+    fs::write(dir.join("test.txt"), "").unwrap();
+    assert_eq!(Snapshot::take(&paths).changes_since(&before), vec![dir.join("input.txt"), dir.join("test.txt")]);
+    fs::remove_dir_all(&dir).unwrap();
+
+    let previous = parse_answers("Day 7: No Space Left On Device\nPart 1: 95437\nPart 2: 1\n");
+    let current = parse_answers("Part 1: 95437\nPart 2: 24933642\n");
+    assert_eq!(diff_answers(&previous, &current), vec!["part 1: 95437 (unchanged)", "part 2: 1 -> 24933642"]);
+}